@@ -0,0 +1,226 @@
+//! Encoders whose variant is picked at runtime.
+//!
+//! ```rust
+//! # use hashid_stack::{any::{AnyHashId, Variant}, prelude::*};
+//! # fn main() {
+//! let variant: Variant = "b32".parse().unwrap();
+//! let ids = AnyHashId::new(variant, b"from config", 8).unwrap();
+//! let id = ids.encode([1, 2, 3]);
+//! assert_eq!(HashIdB32::with_salt_and_len(b"from config", 8).encode([1, 2, 3]), id);
+//! assert_eq!([1, 2, 3], ids.decode(&id).unwrap());
+//! # }
+//! ```
+use crate::{
+	codec::HashIdCodec,
+	encoded::EncodedId,
+	hash::BoundedHashId,
+	util::DecodeErr,
+	variants::{HashId as Hash, HashIdB32, HashIdB64, HashIdDefault, HashIdQr},
+};
+use core::{fmt, str::FromStr};
+
+#[cfg(all(feature = "smartstring", feature = "std"))]
+use smartstring::alias::String;
+
+/// The four variants, named by [`Hash::NAME`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Variant {
+	#[default]
+	Default,
+	Qr,
+	B64,
+	B32,
+}
+
+impl Variant {
+	/// Every variant, in declaration order
+	pub const ALL: [Variant; 4] = [Variant::Default, Variant::Qr, Variant::B64, Variant::B32];
+
+	/// Short name, as parsed by `FromStr`
+	pub fn name(self) -> &'static str {
+		match self {
+			| Variant::Default => HashIdDefault::NAME,
+			| Variant::Qr => HashIdQr::NAME,
+			| Variant::B64 => HashIdB64::NAME,
+			| Variant::B32 => HashIdB32::NAME,
+		}
+	}
+}
+
+impl fmt::Display for Variant {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.name())
+	}
+}
+
+/// Returned when parsing a name that isn't a [`Variant`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnknownVariant;
+
+impl fmt::Display for UnknownVariant {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("unknown hash ID variant, expected one of default, qr, b64 or b32")
+	}
+}
+
+impl core::error::Error for UnknownVariant {}
+
+impl FromStr for Variant {
+	type Err = UnknownVariant;
+	/// Parses a short name, ignoring ASCII case
+	fn from_str(s: &str) -> Result<Self, UnknownVariant> {
+		Variant::ALL
+			.into_iter()
+			.find(|v| v.name().eq_ignore_ascii_case(s))
+			.ok_or(UnknownVariant)
+	}
+}
+
+/// A bounded encoder of any variant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AnyHashId {
+	Default(BoundedHashId<HashIdDefault>),
+	Qr(BoundedHashId<HashIdQr>),
+	B64(BoundedHashId<HashIdB64>),
+	B32(BoundedHashId<HashIdB32>),
+}
+
+/// Runs `$body` with `$ids` bound to the encoder inside `$any`
+macro_rules! dispatch {
+	($any:expr, $ids:ident => $body:expr) => {
+		match $any {
+			| AnyHashId::Default($ids) => $body,
+			| AnyHashId::Qr($ids) => $body,
+			| AnyHashId::B64($ids) => $body,
+			| AnyHashId::B32($ids) => $body,
+		}
+	};
+}
+
+/// Rebuilds `$any` with `$body`, where `$ids` is the encoder inside it
+macro_rules! map {
+	($any:expr, $ids:ident => $body:expr) => {
+		match $any {
+			| AnyHashId::Default($ids) => AnyHashId::Default($body),
+			| AnyHashId::Qr($ids) => AnyHashId::Qr($body),
+			| AnyHashId::B64($ids) => AnyHashId::B64($body),
+			| AnyHashId::B32($ids) => AnyHashId::B32($body),
+		}
+	};
+}
+
+impl AnyHashId {
+	/// Builds an encoder of `variant`, or `None` if the salt is longer than
+	/// [`MAX_SALT`](crate::hash::MAX_SALT).
+	pub fn new(variant: Variant, salt: &[u8], min_len: impl Into<Option<usize>>) -> Option<Self> {
+		let min_len = min_len.into();
+		Some(match variant {
+			| Variant::Default => {
+				Self::Default(HashIdDefault::with_salt_slice_and_len(salt, min_len)?)
+			}
+			| Variant::Qr => Self::Qr(HashIdQr::with_salt_slice_and_len(salt, min_len)?),
+			| Variant::B64 => Self::B64(HashIdB64::with_salt_slice_and_len(salt, min_len)?),
+			| Variant::B32 => Self::B32(HashIdB32::with_salt_slice_and_len(salt, min_len)?),
+		})
+	}
+	pub fn variant(&self) -> Variant {
+		match self {
+			| Self::Default(_) => Variant::Default,
+			| Self::Qr(_) => Variant::Qr,
+			| Self::B64(_) => Variant::B64,
+			| Self::B32(_) => Variant::B32,
+		}
+	}
+	/// See [`HashId::with_context`](crate::hash::HashId::with_context)
+	pub fn with_context(self, context: &[u8]) -> Self {
+		map!(self, ids => ids.with_context(context))
+	}
+	/// See [`HashId::with_max_len`](crate::hash::HashId::with_max_len)
+	pub fn with_max_len(self, len: usize) -> Self {
+		map!(self, ids => ids.with_max_len(len))
+	}
+	/// See [`HashId::mac_len`](crate::hash::HashId::mac_len)
+	pub fn mac_len(&self) -> usize {
+		dispatch!(self, ids => ids.mac_len())
+	}
+	/// See [`HashId::fingerprint`](crate::hash::HashId::fingerprint)
+	pub fn fingerprint(&self, key: &[u8; 16]) -> [u8; 8] {
+		dispatch!(self, ids => ids.fingerprint(key))
+	}
+	/// The encoder as a trait object
+	pub fn codec(&self) -> &dyn HashIdCodec {
+		dispatch!(self, ids => ids)
+	}
+	/// Encode an ID list, see [`HashId::encode`](crate::hash::HashId::encode)
+	#[cfg(feature = "std")]
+	pub fn encode(&self, values: impl AsRef<[u64]>) -> String {
+		dispatch!(self, ids => ids.encode(values))
+	}
+	/// See [`HashId::encode_buf`](crate::hash::HashId::encode_buf)
+	pub fn encode_buf(&self, values: impl AsRef<[u64]>) -> Option<EncodedId> {
+		dispatch!(self, ids => ids.encode_buf(values))
+	}
+	/// See [`HashId::decode`](crate::hash::HashId::decode)
+	pub fn decode<const OUT: usize>(
+		&self,
+		input: impl AsRef<[u8]>,
+	) -> Result<[u64; OUT], DecodeErr<OUT>> {
+		dispatch!(self, ids => ids.decode(input))
+	}
+	/// See [`HashId::decode_fast`](crate::hash::HashId::decode_fast)
+	pub fn decode_fast<const OUT: usize>(
+		&self,
+		input: impl AsRef<[u8]>,
+	) -> Result<[u64; OUT], DecodeErr<OUT>> {
+		dispatch!(self, ids => ids.decode_fast(input))
+	}
+	/// See [`HashId::decode_into`](crate::hash::HashId::decode_into)
+	pub fn decode_into(
+		&self,
+		input: impl AsRef<[u8]>,
+		out: &mut [u64],
+	) -> Result<(), DecodeErr<0>> {
+		dispatch!(self, ids => ids.decode_into(input, out))
+	}
+}
+
+impl HashIdCodec for AnyHashId {
+	fn encode_id(&self, values: &[u64]) -> Option<EncodedId> {
+		self.codec().encode_id(values)
+	}
+	fn decode_into(&self, input: &[u8], out: &mut [u64]) -> Result<(), DecodeErr<0>> {
+		self.codec().decode_into(input, out)
+	}
+	fn variant(&self) -> &'static str {
+		self.codec().variant()
+	}
+	fn alphabet(&self) -> &'static [u8] {
+		self.codec().alphabet()
+	}
+	fn min_len(&self) -> usize {
+		self.codec().min_len()
+	}
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for AnyHashId {
+	fn zeroize(&mut self) {
+		dispatch!(self, ids => ids.zeroize())
+	}
+}
+
+macro_rules! from {
+	($($v:ident($h:ty)),*) => {$(
+		impl From<BoundedHashId<$h>> for AnyHashId {
+			fn from(ids: BoundedHashId<$h>) -> Self {
+				Self::$v(ids)
+			}
+		}
+	)*};
+}
+from!(
+	Default(HashIdDefault),
+	Qr(HashIdQr),
+	B64(HashIdB64),
+	B32(HashIdB32)
+);
@@ -1,7 +1,9 @@
 use crate::util;
 use core::{convert::TryInto, iter::*, num::Wrapping};
 
-
+/// Writes past `N` are dropped but still counted, so `len() > capacity()`
+/// marks a buffer that overflowed. Reads only ever see the first `N` bytes.
+#[derive(Clone, Copy)]
 pub struct ByteVec<const N: usize> {
 	data: [u8; N],
 	idx: Wrapping<usize>,
@@ -47,32 +49,39 @@ impl<const N: usize> ByteVec<N> {
 		self.idx += Wrapping(1);
 	}
 	pub fn get_checked(&self, off: usize) -> Option<&u8> {
-		if off <= self.idx.0 {
-			Some(unsafe { self.data.get_unchecked(off) })
-		} else {
-			None
-		}
+		self.as_slice().get(off)
 	}
+	/// # Safety
+	/// `off` must be within the capacity `N`.
 	pub unsafe fn get(&self, off: usize) -> u8 {
 		*self.data.get_unchecked(off)
 	}
+	/// Inserts at `idx`, which must be within the capacity `N`. A full
+	/// buffer drops its last byte.
 	pub fn insert(&mut self, idx: usize, value: u8) {
-		self.data.copy_within(idx..=self.idx.0, idx + 1);
-		unsafe {
-			*self.data.get_unchecked_mut(idx) = value;
-			self.idx += 1;
-		}
+		let end = self.idx.0.min(N - 1);
+		self.data.copy_within(idx..end, idx + 1);
+		self.data[idx] = value;
+		self.idx += 1;
 	}
 	pub fn len(&self) -> usize {
 		self.idx.0
 	}
-	/// SAFETY: takes whatever is in the buffer and dumps it to output unfiltered.
-	pub unsafe fn display_unchecked(&self) -> display::Display<N> {
-		display::Display(&self)
+	pub fn is_empty(&self) -> bool {
+		self.idx.0 == 0
+	}
+	/// Whether more than `N` bytes were written
+	pub fn overflowed(&self) -> bool {
+		self.idx.0 > N
+	}
+	/// # Safety
+	/// Takes whatever is in the buffer and dumps it to output unfiltered.
+	pub unsafe fn display_unchecked(&self) -> display::Display<'_, N> {
+		display::Display(self)
 	}
 	/// SAFETY: only displays printable ASCII - 0x20..=0x7E
-	pub fn display_ascii(&self) -> display::DisplayChecked<N> {
-		display::DisplayChecked(&self)
+	pub fn display_ascii(&self) -> display::DisplayChecked<'_, N> {
+		display::DisplayChecked(self)
 	}
 	pub fn as_slice(&self) -> &[u8] {
 		self.as_ref()
@@ -82,13 +91,25 @@ impl<const N: usize> ByteVec<N> {
 	}
 }
 
+impl<const N: usize> Default for ByteVec<N> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<const N: usize> core::fmt::Debug for ByteVec<N> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "b\"{}\"", self.as_slice().escape_ascii())
+	}
+}
+
 impl<const N: usize> FromIterator<u8> for ByteVec<N> {
 	fn from_iter<I: IntoIterator<Item = u8>>(i: I) -> Self {
 		let mut bytes: [u8; N] = util::garbage();
 		let mut max = 0;
 		let mut iter = i.into_iter();
-		for (i, (v, s)) in bytes.iter_mut().zip(&mut iter).enumerate() {
-			max = i;
+		for (v, s) in bytes.iter_mut().zip(&mut iter) {
+			max += 1;
 			*v = s;
 		}
 		max += iter.count();
@@ -101,7 +122,8 @@ impl<const N: usize> FromIterator<u8> for ByteVec<N> {
 impl<const N: usize> Extend<u8> for ByteVec< N> {
 	fn extend<I: IntoIterator<Item = u8>>(&mut self, i: I) {
 		let mut iter = i.into_iter();
-		for (v, s) in (&mut self.data[self.idx.0..]).iter_mut().zip(&mut iter) {
+		let free = self.data.get_mut(self.idx.0..).unwrap_or_default();
+		for (v, s) in free.iter_mut().zip(&mut iter) {
 			self.idx += 1;
 			*v = s;
 		}
@@ -114,7 +136,8 @@ impl<const N: usize> Extend<u8> for ByteVec< N> {
 impl<'a, const N: usize> Extend<&'a u8> for ByteVec< N> {
 	fn extend<I: IntoIterator<Item = &'a u8>>(&mut self, i: I) {
 		let mut iter = i.into_iter();
-		for (v, s) in (&mut self.data[self.idx.0..]).iter_mut().zip(&mut iter) {
+		let free = self.data.get_mut(self.idx.0..).unwrap_or_default();
+		for (v, s) in free.iter_mut().zip(&mut iter) {
 			self.idx += 1;
 			*v = *s;
 		}
@@ -128,7 +151,7 @@ impl<'a, const N: usize> Extend<&'a u8> for ByteVec< N> {
 impl<const N: usize> TryInto<[u8; N]> for ByteVec< N> {
 	type Error = (usize, usize);
 	fn try_into(self) -> Result<[u8; N], (usize, usize)> {
-		if self.idx.0 == (N - 1) {
+		if self.idx.0 == N {
 			Ok(self.data)
 		} else {
 			Err((self.idx.0, N))
@@ -138,13 +161,13 @@ impl<const N: usize> TryInto<[u8; N]> for ByteVec< N> {
 impl<const N: usize> AsRef<[u8]> for ByteVec<N> {
 	/// SAFETY: technically incorrect.
 	fn as_ref(&self) -> &[u8] {
-		&self.data[..self.idx.0]
+		&self.data[..self.idx.0.min(N)]
 	}
 }
 
 impl<const N: usize> AsMut<[u8]> for ByteVec<N> {
 	/// SAFETY: technically incorrect.
 	fn as_mut(&mut self) -> &mut [u8] {
-		&mut self.data[..self.idx.0]
+		&mut self.data[..self.idx.0.min(N)]
 	}
 }
@@ -0,0 +1,73 @@
+//! Object-safe access to encoders of any variant, salt and strategy.
+//!
+//! [`HashId`] carries its variant and salt length in its
+//! type, so encoders of different shapes can't share a collection. Through
+//! [`HashIdCodec`] they can:
+//!
+//! ```rust
+//! # use hashid_stack::{codec::HashIdCodec, prelude::*};
+//! # fn main() {
+//! let codecs: Vec<Box<dyn HashIdCodec>> = vec![
+//!     Box::new(HashIdB32::with_salt(b"users")),
+//!     Box::new(HashIdQr::with_salt_and_len(b"orders", 8)),
+//! ];
+//! for codec in &codecs {
+//!     let mut id = String::new();
+//!     codec.encode_to(&[1, 2], &mut id).unwrap();
+//!     let mut out = [0; 2];
+//!     codec.decode_into(id.as_bytes(), &mut out).unwrap();
+//!     assert_eq!([1, 2], out);
+//! }
+//! assert_eq!(["b32", "qr"], [codecs[0].variant(), codecs[1].variant()]);
+//! # }
+//! ```
+use crate::{
+	encoded::EncodedId, hash::HashId, lottery::Lottery, shuffle::Shuffle, util::DecodeErr,
+	variants::HashId as Hash,
+};
+use core::fmt;
+
+/// Encoding and decoding behind a `dyn` pointer.
+pub trait HashIdCodec {
+	/// Encodes `values`, or gives `None` if there are none
+	fn encode_id(&self, values: &[u64]) -> Option<EncodedId>;
+	/// Writes the ID for `values` to `out`. Writes nothing if there are none.
+	fn encode_to(&self, values: &[u64], out: &mut dyn fmt::Write) -> fmt::Result {
+		match self.encode_id(values) {
+			| Some(id) => out.write_str(&id),
+			| None => Ok(()),
+		}
+	}
+	/// Strictly decodes exactly `out.len()` values from `input` into `out`
+	fn decode_into(&self, input: &[u8], out: &mut [u64]) -> Result<(), DecodeErr<0>>;
+	/// Short name of the variant, see [`Hash::NAME`]
+	fn variant(&self) -> &'static str;
+	/// Full alphabet of the variant, separators included
+	fn alphabet(&self) -> &'static [u8];
+	/// Minimum ID length, or 0 for none
+	fn min_len(&self) -> usize;
+}
+
+impl<H: Hash, const SALT: usize, L: Lottery, S: Shuffle> HashIdCodec for HashId<H, SALT, L, S>
+where
+	[(); H::SEP.len()]: Sized,
+	[(); H::REAL - H::GUARDS]: Sized,
+	[(); H::ALPH.len()]: Sized,
+	[(); H::REAL]: Sized,
+{
+	fn encode_id(&self, values: &[u64]) -> Option<EncodedId> {
+		self.encode_inner(values)
+	}
+	fn decode_into(&self, input: &[u8], out: &mut [u64]) -> Result<(), DecodeErr<0>> {
+		HashId::decode_into(self, input, out)
+	}
+	fn variant(&self) -> &'static str {
+		H::NAME
+	}
+	fn alphabet(&self) -> &'static [u8] {
+		H::ALPH
+	}
+	fn min_len(&self) -> usize {
+		HashId::min_len(self)
+	}
+}
@@ -0,0 +1,332 @@
+//! Encoder settings from config files and the environment.
+//!
+//! [`HashIdConfig`] deserializes from any serde format, or loads from
+//! `HASHID_*` environment variables, so every service sets up its encoders
+//! the same way.
+//!
+//! ```rust
+//! # use hashid_stack::{any::Variant, config::HashIdConfig};
+//! # fn main() {
+//! let config: HashIdConfig = serde_json::from_str(r#"{
+//!     "variant": "b32",
+//!     "salt": "c2VjcmV0IHNhbHQ=",
+//!     "salt_encoding": "base64",
+//!     "min_len": 8,
+//!     "context": "user"
+//! }"#).unwrap();
+//! let ids = config.build().unwrap();
+//! assert_eq!(Variant::B32, ids.variant());
+//! assert_eq!([42], ids.decode(ids.encode([42])).unwrap());
+//! # }
+//! ```
+use crate::{
+	any::{AnyHashId, Variant},
+	hash::{BoundedHashId, MAX_SALT},
+	variants::HashId as Hash,
+};
+use ::serde::{Deserialize, Deserializer};
+use core::{fmt, str::FromStr};
+
+pub use crate::hash::MAX_MIN_LEN;
+
+/// How [`HashIdConfig::salt`] is written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SaltEncoding {
+	/// Pairs of hex digits, in either case
+	Hex,
+	/// Standard or URL-safe base64, padding optional
+	Base64,
+	/// The bytes of the string itself
+	#[default]
+	Utf8,
+}
+
+impl FromStr for SaltEncoding {
+	type Err = ();
+	fn from_str(s: &str) -> Result<Self, ()> {
+		match s.to_ascii_lowercase().as_str() {
+			| "hex" => Ok(Self::Hex),
+			| "base64" => Ok(Self::Base64),
+			| "utf8" => Ok(Self::Utf8),
+			| _ => Err(()),
+		}
+	}
+}
+
+/// Settings for one encoder.
+///
+/// Only `salt` is required. The variant defaults to
+/// [`HashIdDefault`](crate::variants::HashIdDefault) and the salt encoding
+/// to UTF-8. With the `zeroize` feature the salt is wiped on drop.
+#[derive(Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HashIdConfig {
+	#[serde(default, deserialize_with = "variant")]
+	pub variant: Variant,
+	/// Secret, at most [`MAX_SALT`] bytes once decoded
+	pub salt: String,
+	#[serde(default)]
+	pub salt_encoding: SaltEncoding,
+	/// See [`HashId::min_len`](crate::hash::HashId::min_len), at most [`MAX_MIN_LEN`]
+	#[serde(default)]
+	pub min_len: Option<usize>,
+	/// See [`HashId::with_max_len`](crate::hash::HashId::with_max_len)
+	#[serde(default)]
+	pub max_len: Option<usize>,
+	/// See [`HashId::with_context`](crate::hash::HashId::with_context)
+	#[serde(default)]
+	pub context: Option<String>,
+}
+
+/// Deserializes a [`Variant`] from its name
+fn variant<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Variant, D::Error> {
+	String::deserialize(deserializer)?
+		.parse()
+		.map_err(::serde::de::Error::custom)
+}
+
+/// Why a [`HashIdConfig`] couldn't be loaded or built.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConfigError {
+	/// A required variable isn't set. Holds its name.
+	Missing(String),
+	/// A variable couldn't be parsed. Holds its name.
+	Invalid(String),
+	/// The salt isn't valid in its encoding
+	SaltEncoding(SaltEncoding),
+	/// The decoded salt is longer than [`MAX_SALT`]. Holds its length.
+	SaltTooLong(usize),
+	/// `min_len` is over [`MAX_MIN_LEN`]
+	MinLen(usize),
+	/// `min_len` is over `max_len`
+	Lengths { min_len: usize, max_len: usize },
+	/// [`HashIdConfig::build_as`] was asked for another variant
+	Variant { expected: Variant, found: Variant },
+}
+
+impl fmt::Display for ConfigError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			| Self::Missing(name) => write!(f, "{} is not set", name),
+			| Self::Invalid(name) => write!(f, "{} is not valid", name),
+			| Self::SaltEncoding(encoding) => write!(f, "salt is not valid {:?}", encoding),
+			| Self::SaltTooLong(len) => {
+				write!(
+					f,
+					"salt is {} bytes, longer than the {} allowed",
+					len, MAX_SALT
+				)
+			}
+			| Self::MinLen(len) => write!(f, "min_len {} is over the {} allowed", len, MAX_MIN_LEN),
+			| Self::Lengths { min_len, max_len } => {
+				write!(f, "min_len {} is over max_len {}", min_len, max_len)
+			}
+			| Self::Variant { expected, found } => {
+				write!(f, "configured for variant {}, not {}", found, expected)
+			}
+		}
+	}
+}
+
+impl std::error::Error for ConfigError {}
+
+impl HashIdConfig {
+	pub fn new(variant: Variant, salt: impl Into<String>) -> Self {
+		Self {
+			variant,
+			salt: salt.into(),
+			salt_encoding: SaltEncoding::default(),
+			min_len: None,
+			max_len: None,
+			context: None,
+		}
+	}
+	/// Loads from `HASHID_VARIANT`, `HASHID_SALT`, `HASHID_SALT_ENCODING`,
+	/// `HASHID_MIN_LEN`, `HASHID_MAX_LEN` and `HASHID_CONTEXT`. Only
+	/// `HASHID_SALT` is required.
+	pub fn from_env() -> Result<Self, ConfigError> {
+		Self::from_env_prefixed("HASHID_")
+	}
+	/// Same as [`HashIdConfig::from_env`], with `prefix` in place of
+	/// `HASHID_`, e.g. `HASHID_USER_` for one encoder among several.
+	pub fn from_env_prefixed(prefix: &str) -> Result<Self, ConfigError> {
+		Self::from_vars(prefix, |name| std::env::var(name).ok())
+	}
+	/// Same as [`HashIdConfig::from_env_prefixed`], reading variables
+	/// through `lookup`.
+	pub fn from_vars(
+		prefix: &str,
+		lookup: impl Fn(&str) -> Option<String>,
+	) -> Result<Self, ConfigError> {
+		let var = |key: &str| {
+			let name = format!("{}{}", prefix, key);
+			(lookup(&name), name)
+		};
+		fn parse<T: FromStr>(
+			(value, name): (Option<String>, String),
+		) -> Result<Option<T>, ConfigError> {
+			value
+				.map(|v| v.trim().parse().map_err(|_| ConfigError::Invalid(name)))
+				.transpose()
+		}
+		let salt = match var("SALT") {
+			| (Some(salt), _) => salt,
+			| (None, name) => return Err(ConfigError::Missing(name)),
+		};
+		Ok(Self {
+			variant: parse(var("VARIANT"))?.unwrap_or_default(),
+			salt,
+			salt_encoding: parse(var("SALT_ENCODING"))?.unwrap_or_default(),
+			min_len: parse(var("MIN_LEN"))?,
+			max_len: parse(var("MAX_LEN"))?,
+			context: var("CONTEXT").0,
+		})
+	}
+	/// The salt decoded from `salt_encoding`
+	pub fn salt_bytes(&self) -> Result<Vec<u8>, ConfigError> {
+		self.decoded_salt().map(|salt| salt.0.clone())
+	}
+	fn decoded_salt(&self) -> Result<SaltBytes, ConfigError> {
+		let salt = match self.salt_encoding {
+			| SaltEncoding::Utf8 => Some(self.salt.as_bytes().to_vec()),
+			| SaltEncoding::Hex => hex(&self.salt),
+			| SaltEncoding::Base64 => base64(&self.salt),
+		};
+		salt.map(SaltBytes)
+			.ok_or(ConfigError::SaltEncoding(self.salt_encoding))
+	}
+	/// Checks the salt and lengths, as done when building
+	pub fn validate(&self) -> Result<(), ConfigError> {
+		self.checked_salt().map(drop)
+	}
+	fn checked_salt(&self) -> Result<SaltBytes, ConfigError> {
+		let salt = self.decoded_salt()?;
+		if salt.0.len() > MAX_SALT {
+			return Err(ConfigError::SaltTooLong(salt.0.len()));
+		}
+		let min_len = self.min_len.unwrap_or(0);
+		if min_len > MAX_MIN_LEN {
+			return Err(ConfigError::MinLen(min_len));
+		}
+		match self.max_len {
+			| Some(max_len) if max_len != 0 && min_len > max_len => {
+				Err(ConfigError::Lengths { min_len, max_len })
+			}
+			| _ => Ok(salt),
+		}
+	}
+	/// Builds the encoder of the configured variant
+	pub fn build(&self) -> Result<AnyHashId, ConfigError> {
+		let salt = self.checked_salt()?;
+		// SAFETY: `checked_salt` limits the salt to `MAX_SALT`.
+		let mut ids =
+			unsafe { AnyHashId::new(self.variant, &salt.0, self.min_len).unwrap_unchecked() };
+		if let Some(max_len) = self.max_len {
+			ids = ids.with_max_len(max_len);
+		}
+		if let Some(context) = &self.context {
+			ids = ids.with_context(context.as_bytes());
+		}
+		Ok(ids)
+	}
+	/// Builds the encoder as `H`, failing if another variant is configured
+	pub fn build_as<H: Hash>(&self) -> Result<BoundedHashId<H>, ConfigError>
+	where
+		[(); H::SEP.len()]: Sized,
+		[(); H::ALPH.len()]: Sized,
+		[(); H::REAL]: Sized,
+		[(); H::REAL - H::GUARDS]: Sized,
+	{
+		if self.variant.name() != H::NAME {
+			return Err(ConfigError::Variant {
+				expected: H::NAME.parse().unwrap_or_default(),
+				found: self.variant,
+			});
+		}
+		let salt = self.checked_salt()?;
+		// SAFETY: `checked_salt` limits the salt to `MAX_SALT`.
+		let mut ids =
+			unsafe { H::with_salt_slice_and_len(&salt.0, self.min_len).unwrap_unchecked() };
+		if let Some(max_len) = self.max_len {
+			ids = ids.with_max_len(max_len);
+		}
+		if let Some(context) = &self.context {
+			ids = ids.with_context(context.as_bytes());
+		}
+		Ok(ids)
+	}
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for HashIdConfig {
+	fn drop(&mut self) {
+		zeroize::Zeroize::zeroize(&mut self.salt);
+	}
+}
+
+/// Decoded salt, wiped on drop with the `zeroize` feature
+struct SaltBytes(Vec<u8>);
+
+#[cfg(feature = "zeroize")]
+impl Drop for SaltBytes {
+	fn drop(&mut self) {
+		zeroize::Zeroize::zeroize(&mut self.0);
+	}
+}
+
+impl fmt::Debug for HashIdConfig {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("HashIdConfig")
+			.field("variant", &self.variant)
+			.field(
+				"salt",
+				&format_args!("<redacted, {} chars>", self.salt.len()),
+			)
+			.field("salt_encoding", &self.salt_encoding)
+			.field("min_len", &self.min_len)
+			.field("max_len", &self.max_len)
+			.field("context", &self.context)
+			.finish()
+	}
+}
+
+/// Decodes pairs of hex digits
+fn hex(s: &str) -> Option<Vec<u8>> {
+	let digit = |c: u8| (c as char).to_digit(16);
+	let s = s.as_bytes();
+	if s.len() % 2 == 1 {
+		return None;
+	}
+	s.chunks(2)
+		.map(|p| Some((digit(p[0])? << 4 | digit(p[1])?) as u8))
+		.collect()
+}
+
+/// Decodes standard or URL-safe base64, with or without padding
+fn base64(s: &str) -> Option<Vec<u8>> {
+	let value = |c: u8| {
+		Some(match c {
+			| b'A'..=b'Z' => c - b'A',
+			| b'a'..=b'z' => c - b'a' + 26,
+			| b'0'..=b'9' => c - b'0' + 52,
+			| b'+' | b'-' => 62,
+			| b'/' | b'_' => 63,
+			| _ => return None,
+		} as u32)
+	};
+	let s = s.trim_end_matches('=').as_bytes();
+	if s.len() % 4 == 1 {
+		return None;
+	}
+	let mut out = Vec::with_capacity(s.len() * 3 / 4);
+	for chunk in s.chunks(4) {
+		let mut acc = 0;
+		for &c in chunk {
+			acc = acc << 6 | value(c)?;
+		}
+		acc <<= 6 * (4 - chunk.len());
+		out.extend_from_slice(&acc.to_be_bytes()[1..chunk.len()]);
+	}
+	Some(out)
+}
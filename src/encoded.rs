@@ -0,0 +1,131 @@
+//! Encoded IDs as returned by the encoders.
+use crate::hash::Buffer;
+use core::{borrow::Borrow, cmp::Ordering, fmt, hash, ops::Deref};
+
+/// An encoded ID.
+///
+/// Only the encoders create these, and everything they write comes from a
+/// variant's alphabet, so the contents are always ASCII. It derefs to `str`,
+/// compares and hashes as one, and can be looked up by `&str` in maps.
+///
+/// ```rust
+/// # use hashid_stack::prelude::*;
+/// # use std::collections::HashMap;
+/// # fn main() {
+/// let ids = HashIdB64::with_salt(b"1 2 3 4");
+/// let id = ids.encode_buf([1, 2, 3]).unwrap();
+/// assert_eq!(ids.encode([1, 2, 3]), id.to_string());
+/// assert!(id == *ids.encode([1, 2, 3]));
+///
+/// let names = HashMap::from([(id, "first")]);
+/// assert_eq!(Some(&"first"), names.get(&*ids.encode([1, 2, 3])));
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct EncodedId(Buffer);
+
+impl EncodedId {
+	/// Wraps encoder output, which must be ASCII.
+	pub(crate) fn new(buf: Buffer) -> Self {
+		debug_assert!(buf.as_slice().is_ascii());
+		Self(buf)
+	}
+	pub fn as_str(&self) -> &str {
+		// SAFETY: only ever built from encoder output, which is ASCII.
+		unsafe { core::str::from_utf8_unchecked(self.0.as_slice()) }
+	}
+	pub fn as_bytes(&self) -> &[u8] {
+		self.0.as_slice()
+	}
+	/// The underlying buffer
+	pub fn into_inner(self) -> Buffer {
+		self.0
+	}
+}
+
+impl Deref for EncodedId {
+	type Target = str;
+	fn deref(&self) -> &str {
+		self.as_str()
+	}
+}
+
+impl AsRef<str> for EncodedId {
+	fn as_ref(&self) -> &str {
+		self.as_str()
+	}
+}
+
+impl AsRef<[u8]> for EncodedId {
+	fn as_ref(&self) -> &[u8] {
+		self.as_bytes()
+	}
+}
+
+impl Borrow<str> for EncodedId {
+	fn borrow(&self) -> &str {
+		self.as_str()
+	}
+}
+
+impl fmt::Display for EncodedId {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl fmt::Debug for EncodedId {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Debug::fmt(self.as_str(), f)
+	}
+}
+
+impl PartialEq for EncodedId {
+	fn eq(&self, other: &Self) -> bool {
+		self.as_str() == other.as_str()
+	}
+}
+impl Eq for EncodedId {}
+
+impl PartialEq<str> for EncodedId {
+	fn eq(&self, other: &str) -> bool {
+		self.as_str() == other
+	}
+}
+
+impl PartialEq<&str> for EncodedId {
+	fn eq(&self, other: &&str) -> bool {
+		self.as_str() == *other
+	}
+}
+
+impl PartialEq<EncodedId> for str {
+	fn eq(&self, other: &EncodedId) -> bool {
+		self == other.as_str()
+	}
+}
+
+impl PartialEq<EncodedId> for &str {
+	fn eq(&self, other: &EncodedId) -> bool {
+		*self == other.as_str()
+	}
+}
+
+impl PartialOrd for EncodedId {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for EncodedId {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.as_str().cmp(other.as_str())
+	}
+}
+
+/// Hashes as the `str`, as `Borrow<str>` requires.
+impl hash::Hash for EncodedId {
+	fn hash<H: hash::Hasher>(&self, state: &mut H) {
+		self.as_str().hash(state)
+	}
+}
@@ -0,0 +1,99 @@
+//! Format preserving permutation of single values.
+//!
+//! Each value is permuted within the range of values that have the same
+//! number of digits in the encoding base, so permuted IDs keep their length.
+//! The permutation is a balanced Feistel network over just enough bits to
+//! cover that range, with SipHash-2-4 as round function, and cycle walking to
+//! stay inside it.
+use crate::sip::Sip;
+
+const ROUNDS: u64 = 8;
+
+/// Keyed permutation, see `HashId::with_fpe`
+#[derive(Clone, Copy)]
+pub(crate) struct Fpe {
+	pub(crate) key: [u64; 2],
+}
+
+impl Fpe {
+	/// Derives the permutation key from the salt and context digest
+	pub(crate) fn from_salt(salt: &[u8], context: &[u8]) -> Self {
+		let half = |label: &[u8]| Sip::new(0, 0).write(label).write(salt).write(context).finish();
+		Self {
+			key: [half(b"hashid-stack fpe k0"), half(b"hashid-stack fpe k1")],
+		}
+	}
+	/// Permutes `value`, the `pos`th in its ID, keeping its digit count in `base`.
+	pub(crate) fn encrypt(&self, pos: usize, base: u64, value: u64) -> u64 {
+		self.walk(pos, base, value, Self::forward)
+	}
+	/// Inverse of [`Fpe::encrypt`]
+	pub(crate) fn decrypt(&self, pos: usize, base: u64, value: u64) -> u64 {
+		self.walk(pos, base, value, Self::backward)
+	}
+
+	fn walk(&self, pos: usize, base: u64, value: u64, step: fn(&Self, Tweak, u64) -> u64) -> u64 {
+		let (lo, hi) = bucket(base, value);
+		// number of values in the bucket, minus one so the top bucket fits
+		let span = hi - lo;
+		// smallest even bit count covering the bucket
+		let bits = (u64::BITS - span.leading_zeros()).next_multiple_of(2);
+		let tweak = Tweak { pos, lo, half: bits / 2 };
+		let mut x = value - lo;
+		loop {
+			x = step(self, tweak, x);
+			if x <= span {
+				return x + lo;
+			}
+		}
+	}
+	fn round(&self, tweak: Tweak, round: u64, input: u64) -> u64 {
+		Sip::new(self.key[0], self.key[1])
+			.write_u64(tweak.pos as u64)
+			.write_u64(tweak.lo)
+			.write_u64(round)
+			.write_u64(input)
+			.finish() & tweak.mask()
+	}
+	fn forward(&self, tweak: Tweak, x: u64) -> u64 {
+		let (mut l, mut r) = (x >> tweak.half, x & tweak.mask());
+		for round in 0..ROUNDS {
+			(l, r) = (r, l ^ self.round(tweak, round, r));
+		}
+		(l << tweak.half) | r
+	}
+	fn backward(&self, tweak: Tweak, x: u64) -> u64 {
+		let (mut l, mut r) = (x >> tweak.half, x & tweak.mask());
+		for round in (0..ROUNDS).rev() {
+			(l, r) = (r ^ self.round(tweak, round, l), l);
+		}
+		(l << tweak.half) | r
+	}
+}
+
+/// Per-value inputs to the round function
+#[derive(Clone, Copy)]
+struct Tweak {
+	pos: usize,
+	lo: u64,
+	/// Bits in each Feistel half, at most 32
+	half: u32,
+}
+
+impl Tweak {
+	fn mask(&self) -> u64 {
+		(1 << self.half) - 1
+	}
+}
+
+/// Smallest and largest value with as many digits in `base` as `value`
+fn bucket(base: u64, value: u64) -> (u64, u64) {
+	if value < base {
+		return (0, base - 1);
+	}
+	let mut lo = base;
+	while let Some(next) = lo.checked_mul(base).filter(|&next| next <= value) {
+		lo = next;
+	}
+	(lo, lo.checked_mul(base).map_or(u64::MAX, |hi| hi - 1))
+}
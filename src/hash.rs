@@ -1,39 +1,131 @@
-use crate::{bytevec::ByteVec, util, variants::HashId as Hash};
-use core::{cmp::Ordering, hint::unreachable_unchecked, marker::PhantomData, num::NonZeroUsize};
+use crate::{
+	bytevec::ByteVec,
+	encoded::EncodedId,
+	fpe::Fpe,
+	lottery::{Classic, Lottery},
+	shuffle::{Consistent, Shuffle},
+	sip::Sip,
+	util,
+	variants::HashId as Hash,
+};
+use core::{cmp::Ordering, fmt, hint::unreachable_unchecked, marker::PhantomData, num::NonZeroUsize};
 
 #[cfg(all(feature = "smartstring", feature = "std"))]
 use smartstring::alias::String;
 
 /// Default bytevec length when returning from an encode function.
 /// Set to house option + usize extra within 64B by default.
-const BV_L_D: usize = 127 - core::mem::size_of::<usize>();
-#[derive(Clone, Copy, Debug)]
+pub(crate) const BV_L_D: usize = 127 - core::mem::size_of::<usize>();
+/// Buffer returned by the encode functions.
+pub type Buffer = ByteVec<BV_L_D>;
+
+/// Largest salt accepted by the runtime-length constructors.
+pub const MAX_SALT: usize = 64;
+
+/// Longest tag [`HashId::with_mac`] will append.
+pub const MAX_MAC: usize = 8;
+
+/// Longest `min_len` accepted, the capacity of a [`Buffer`]. A MAC tag or
+/// check character lowers it further, see [`HashId::max_min_len`].
+pub const MAX_MIN_LEN: usize = BV_L_D;
+
+/// Domain separation for the MAC input.
+const MAC_CONTEXT: &[u8] = b"hashid-stack mac v1";
+
+/// Domain separation for [`HashId::with_context`] digests.
+const CONTEXT_LABEL: &[u8] = b"hashid-stack context v1";
+
+/// Domain separation for [`HashId::encode_with_tweak`] digests.
+const TWEAK_LABEL: &[u8] = b"hashid-stack tweak v1";
+
+/// Domain separation for [`HashId::fingerprint`].
+const FINGERPRINT_LABEL: &[u8] = b"hashid-stack fingerprint v1";
+
+/// Copies the start of `b` into an array; `b` must be at least `N` long.
+fn array<const N: usize>(b: &[u8]) -> [u8; N] {
+	unsafe { b.get_unchecked(..N).try_into().unwrap_unchecked() }
+}
+
+/// Hash ID whose salt length is picked at runtime, up to [`MAX_SALT`] bytes.
+///
+/// Every salt shares the one type, so encoders for different salts can live
+/// in the same collection. Output is identical to a `HashId` built from an
+/// exactly-sized salt array.
+///
+/// ```rust
+/// # use hashid_stack::prelude::*;
+/// # fn main() {
+/// let salt = String::from("1 2 3 4");
+/// let runtime: BoundedHashId<HashIdB64> = HashIdB64::with_salt_slice(salt.as_bytes()).unwrap();
+/// let fixed = HashIdB64::with_salt(b"1 2 3 4");
+/// assert_eq!(runtime.encode([1, 2, 3]), fixed.encode([1, 2, 3]));
+/// # }
+/// ```
+pub type BoundedHashId<H> = HashId<H, MAX_SALT>;
+
+/// Hash ID encoder and decoder for the variant `H`.
+///
+/// `L` picks the lottery character, see [`HashId::with_lottery`], and `S`
+/// shuffles the alphabet, see [`HashId::with_shuffle`].
+///
+/// The salt and everything derived from it are secret. `Debug` output only
+/// shows the variant and non-secret settings. With the `zeroize` feature,
+/// wrap it in [`Zeroizing`](https://docs.rs/zeroize/latest/zeroize/struct.Zeroizing.html)
+/// to wipe it on drop; being `Copy`, it can't do so itself.
+#[derive(Clone, Copy)]
 pub struct HashId<
 	H: Hash,
 	const SALT: usize,
+	L: Lottery = Classic,
+	S: Shuffle = Consistent,
 	// const BV_L: usize = BV_L_D
 > where
 	[(); H::SEP.len()]: Sized,
 	[(); H::REAL - H::GUARDS]: Sized,
 {
-	/// Internal, set by user. Must have a defined length.
+	/// Internal, set by user. Holds at most `SALT` bytes.
 	salt: [u8; SALT],
+	/// Number of bytes of `salt` in use.
+	salt_len: usize,
 	min_len: Option<NonZeroUsize>,
+	max_len: Option<NonZeroUsize>,
 
 	hasher: PhantomData<H>,
+	lottery: PhantomData<L>,
+	shuffle: PhantomData<S>,
 	alphabet: [u8; H::REAL - H::GUARDS],
 	separators: [u8; H::SEP.len()],
 	guards: [u8; H::GUARDS],
+	mac: Option<Mac>,
+	check: bool,
+	fpe: Option<Fpe>,
+	random: bool,
+	/// Digest of the context set by [`HashId::with_context`]
+	context: Option<[u8; 8]>,
+	/// Digest of the tweak, only set on the copy made for one call by
+	/// [`HashId::encode_with_tweak`] and [`HashId::decode_with_tweak`]
+	tweak: Option<[u8; 8]>,
+}
+
+/// Key and tag length set by [`HashId::with_mac`]
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Mac {
+	key: [u8; 16],
+	len: usize,
 }
 
 impl<
 		H: Hash,
 		const SALT: usize,
+		L: Lottery,
+		S: Shuffle,
 		//const BV_L: usize
 	>
 	HashId<
 		H,
-		SALT, //, BV_L
+		SALT,
+		L,
+		S, //, BV_L
 	> where
 	[(); H::SEP.len()]: Sized,
 	[(); H::REAL - H::GUARDS]: Sized,
@@ -59,41 +151,333 @@ impl<
 		self
 	}
 	*/
-	fn new(
-		salt: [u8; SALT],
-		min_len: Option<NonZeroUsize>,
-		alphabet: [u8; H::REAL - H::GUARDS],
-		separators: [u8; H::SEP.len()],
-		guards: [u8; H::GUARDS],
-	) -> Self {
-		Self {
+	fn new(salt: [u8; SALT], salt_len: usize, min_len: Option<NonZeroUsize>) -> Self {
+		let mut new = Self {
 			salt,
+			salt_len,
 			min_len,
+			max_len: None,
 			hasher: PhantomData,
-			alphabet,
-			separators,
-			guards,
-		}
+			lottery: PhantomData,
+			shuffle: PhantomData,
+			alphabet: util::garbage(),
+			separators: util::garbage(),
+			guards: util::garbage(),
+			mac: None,
+			check: false,
+			fpe: None,
+			random: false,
+			context: None,
+			tweak: None,
+		};
+		new.shuffle_alphabet();
+		new
 	}
 
 	pub(crate) fn init_salt_len(salt: &[u8; SALT], min_len: Option<usize>) -> Self {
-		// shortcut for slices
-		fn to<const N: usize>(b: &[u8]) -> [u8; N] {
-			unsafe { b.try_into().unwrap_unchecked() }
-		}
-		let mut seps: [_; H::SEP.len()] = to(H::SEP);
+		// SAFETY: the array is exactly `SALT` long.
+		unsafe { Self::init_salt_slice(salt, min_len).unwrap_unchecked() }
+	}
+
+	/// Same as `init_salt_len`, but fails if the salt is longer than `SALT`.
+	pub(crate) fn init_salt_slice(salt: &[u8], min_len: Option<usize>) -> Option<Self> {
+		let mut buf: [u8; SALT] = util::garbage();
+		buf.get_mut(..salt.len())?.copy_from_slice(salt);
+		let min_len = min_len.map(|len| len.min(MAX_MIN_LEN));
+		Some(HashId::new(buf, salt.len(), min_len.and_then(NonZeroUsize::new)))
+	}
+	/// Shuffles the alphabet, separators and guards from the salt, then
+	/// from the context if one is set.
+	fn shuffle_alphabet(&mut self) {
+		let mut seps: [_; H::SEP.len()] = array(H::SEP);
 		let mut alph: [_; H::REAL] = H::filtered();
 
-		util::shuffle(&mut seps, salt);
-		util::shuffle(&mut alph, salt);
-		let guards = to(&alph[..H::GUARDS]);
-		HashId::new(
-			*salt,
-			min_len.and_then(NonZeroUsize::new),
-			to(&alph[H::GUARDS..]),
-			seps,
-			guards,
-		)
+		S::shuffle(&mut seps, self.salt());
+		S::shuffle(&mut alph, self.salt());
+		if let Some(context) = &self.context {
+			S::shuffle(&mut seps, context);
+			S::shuffle(&mut alph, context);
+		}
+		self.separators = seps;
+		self.guards = array(&alph[..H::GUARDS]);
+		self.alphabet = array(&alph[H::GUARDS..]);
+	}
+	/// Switches the strategy picking the lottery character.
+	///
+	/// Decoding works the same with any strategy, but [`HashId::decode`]
+	/// only accepts IDs encoded with the strategy set here.
+	pub fn with_lottery<L2: Lottery>(self) -> HashId<H, SALT, L2, S> {
+		HashId {
+			salt: self.salt,
+			salt_len: self.salt_len,
+			min_len: self.min_len,
+			max_len: self.max_len,
+			hasher: PhantomData,
+			lottery: PhantomData,
+			shuffle: PhantomData,
+			alphabet: self.alphabet,
+			separators: self.separators,
+			guards: self.guards,
+			mac: self.mac,
+			check: self.check,
+			fpe: self.fpe,
+			random: self.random,
+			context: self.context,
+			tweak: self.tweak,
+		}
+	}
+	/// Switches the algorithm shuffling the alphabet.
+	///
+	/// The alphabet, separators and guards are shuffled again from the salt,
+	/// so IDs from different shuffles don't decode under each other.
+	/// Everything else set on this instance carries over.
+	pub fn with_shuffle<S2: Shuffle>(self) -> HashId<H, SALT, L, S2> {
+		let mut new = HashId {
+			salt: self.salt,
+			salt_len: self.salt_len,
+			min_len: self.min_len,
+			max_len: self.max_len,
+			hasher: PhantomData,
+			lottery: PhantomData,
+			shuffle: PhantomData,
+			alphabet: self.alphabet,
+			separators: self.separators,
+			guards: self.guards,
+			mac: self.mac,
+			check: self.check,
+			fpe: self.fpe,
+			random: self.random,
+			context: self.context,
+			tweak: self.tweak,
+		};
+		new.shuffle_alphabet();
+		new
+	}
+	/// Separates IDs by purpose, so that one salt can serve several
+	/// resource types without their IDs being accepted for one another.
+	///
+	/// The context is hashed, and the digest shuffles the alphabet again
+	/// after the salt and extends the key for every value. It also keys
+	/// [`HashId::with_fpe`] and goes into the [`HashId::with_mac`] tag.
+	/// Setting a new context replaces the previous one.
+	///
+	/// An ID from another context decodes to an unrelated number if at all,
+	/// as most strings over the alphabet are valid IDs. Add a MAC to have
+	/// them rejected outright.
+	///
+	/// ```rust
+	/// # use hashid_stack::prelude::*;
+	/// # fn main() {
+	/// let ids = HashIdB64::with_salt(b"1 2 3 4");
+	/// let (users, orders) = (ids.with_context(b"user"), ids.with_context(b"order"));
+	/// let user = users.encode([42]);
+	/// assert_ne!(user, orders.encode([42]));
+	/// assert_eq!([42], users.decode(&user).unwrap());
+	/// assert!(orders.decode::<1>(&user).is_err());
+	/// # }
+	/// ```
+	pub fn with_context(mut self, context: &[u8]) -> Self {
+		let digest = Sip::new(0, 0).write(CONTEXT_LABEL).write(context).finish();
+		self.context = Some(digest.to_le_bytes());
+		self.shuffle_alphabet();
+		if self.fpe.is_some() {
+			self = self.with_fpe();
+		}
+		self
+	}
+	/// The context digest, or nothing if no context is set
+	fn context(&self) -> &[u8] {
+		self.context.as_ref().map_or(&[], |c| c)
+	}
+	/// Appends a keyed MAC of `len` characters to every ID.
+	///
+	/// The tag is SipHash-2-4 over the values, truncated and written in the
+	/// variant's alphabet after any `min_len` padding. Both [`HashId::decode`]
+	/// and [`HashId::decode_fast`] check it in constant time and fail with
+	/// [`DecodeErr::Mac`](util::DecodeErr::Mac) if it doesn't match, so IDs
+	/// can't be forged or walked without the key.
+	///
+	/// `len` is clamped to `1..=MAX_MAC`. Each character adds
+	/// `log2(H::ALPH.len())` bits, so pick it for the variant in use. The
+	/// tag lowers [`HashId::max_min_len`] by its length.
+	///
+	/// ```rust
+	/// # use hashid_stack::prelude::*;
+	/// # fn main() {
+	/// let ids = HashIdB64::with_salt(b"1 2 3 4").with_mac(*b"sixteen byte key", 6);
+	/// let id = ids.encode([7]);
+	/// assert_eq!([7], ids.decode(&id).unwrap());
+	///
+	/// let forged = format!("{}AAAAAA", HashIdB64::with_salt(b"1 2 3 4").encode([8]));
+	/// assert!(matches!(ids.decode::<1>(forged), Err(DecodeErr::Mac)));
+	/// # }
+	/// ```
+	pub fn with_mac(mut self, key: [u8; 16], len: usize) -> Self {
+		self.mac = Some(Mac {
+			key,
+			len: len.clamp(1, MAX_MAC),
+		});
+		self.clamp_min_len()
+	}
+	/// Computes the MAC tag for the given values. Only `mac.len` bytes are used.
+	fn mac_tag(&self, mac: &Mac, values: &[u64]) -> [u8; MAX_MAC] {
+		let mut sip = Sip::with_key(&mac.key);
+		sip.write(MAC_CONTEXT).write(self.context()).write(self.tweak());
+		sip.write_u64(values.len() as u64);
+		for &v in values {
+			sip.write_u64(v);
+		}
+		let mut hash = sip.finish();
+		let mut tag = [0; MAX_MAC];
+		for t in tag.iter_mut().take(mac.len) {
+			*t = *unsafe { H::ALPH.get_unchecked(hash as usize % H::ALPH.len()) };
+			hash /= H::ALPH.len() as u64;
+		}
+		tag
+	}
+	/// Appends a check character to every ID, so typos can be told apart
+	/// from IDs that are well formed but unknown.
+	///
+	/// The check character is ISO 7064 hybrid MOD (M+1, M) over the whole
+	/// ID, where M is the size of the variant's alphabet: MOD 63,62 for
+	/// [`HashIdDefault`](crate::variants::HashIdDefault), MOD 65,64 for
+	/// [`HashIdB64`](crate::variants::HashIdB64) and so on. It catches every
+	/// single wrong character and most swapped neighbours, which decode
+	/// reports as [`DecodeErr::Check`](util::DecodeErr::Check) rather than
+	/// going on to decode some other value.
+	///
+	/// ```rust
+	/// # use hashid_stack::prelude::*;
+	/// # fn main() {
+	/// let ids = HashIdB32::with_salt(b"1 2 3 4").with_check_char();
+	/// let id = ids.encode([1234]);
+	/// assert_eq!([1234], ids.decode(&id).unwrap());
+	///
+	/// let typo = id.replacen(&id[..1], if &id[..1] == "A" { "B" } else { "A" }, 1);
+	/// assert!(matches!(ids.decode::<1>(typo), Err(DecodeErr::Check)));
+	/// # }
+	/// ```
+	pub fn with_check_char(mut self) -> Self {
+		self.check = true;
+		self.clamp_min_len()
+	}
+	/// Computes the check character for `input`, or `None` if it holds
+	/// characters outside the alphabet.
+	fn check_char(input: &[u8]) -> Option<u8> {
+		let m = H::ALPH.len();
+		// Doubling only permutes the residues when M + 1 is odd. Otherwise
+		// tripling does, as M + 1 is then even and never a multiple of three
+		// for the alphabets we ship.
+		let k = if m % 2 == 0 { 2 } else { 3 };
+		let p = input.iter().try_fold(m, |p, c| {
+			let v = H::ALPH.iter().position(|a| a == c)?;
+			let s = match (p + v) % m {
+				| 0 => m,
+				| s => s,
+			};
+			Some(k * s % (m + 1))
+		})?;
+		H::ALPH.get((m + 1 - p) % m).copied()
+	}
+	/// Encrypts every value with a keyed permutation before encoding.
+	///
+	/// Plain hashids only shuffle the alphabet, so sequential values still
+	/// produce visibly related IDs. With this set, each value first goes
+	/// through a Feistel permutation keyed from the salt and tweaked by its
+	/// position, which maps it to another value with the same number of
+	/// digits. IDs keep their alphabet and length, but neighbouring values
+	/// no longer look alike.
+	///
+	/// ```rust
+	/// # use hashid_stack::prelude::*;
+	/// # fn main() {
+	/// let plain = HashIdB64::with_salt(b"1 2 3 4");
+	/// let ids = plain.with_fpe();
+	/// assert_eq!(plain.encode([1000]).len(), ids.encode([1000]).len());
+	/// assert_ne!(plain.encode([1000]), ids.encode([1000]));
+	/// assert_eq!([1000], ids.decode(ids.encode([1000])).unwrap());
+	/// # }
+	/// ```
+	pub fn with_fpe(mut self) -> Self {
+		self.fpe = Some(Fpe::from_salt(self.salt(), self.context()));
+		self
+	}
+	/// Applies the `with_fpe` permutation to the `pos`th value, if set
+	fn permute(&self, pos: usize, value: u64) -> u64 {
+		match &self.fpe {
+			| Some(fpe) => fpe.encrypt(pos, (H::REAL - H::GUARDS) as u64, value),
+			| None => value,
+		}
+	}
+	/// Reverses [`HashId::permute`]
+	fn unpermute(&self, pos: usize, value: u64) -> u64 {
+		match &self.fpe {
+			| Some(fpe) => fpe.decrypt(pos, (H::REAL - H::GUARDS) as u64, value),
+			| None => value,
+		}
+	}
+	/// Minimum ID length set on construction, or 0 for none. At most
+	/// [`HashId::max_min_len`].
+	pub fn min_len(&self) -> usize {
+		self.min_len.map_or(0, NonZeroUsize::get)
+	}
+	/// Longest `min_len` that leaves room in a [`Buffer`] for the MAC tag
+	/// and check character
+	pub fn max_min_len(&self) -> usize {
+		MAX_MIN_LEN - self.mac.map_or(0, |mac| mac.len) - self.check as usize
+	}
+	/// Length of the tag set by [`HashId::with_mac`], or 0 for none
+	pub fn mac_len(&self) -> usize {
+		self.mac.map_or(0, |mac| mac.len)
+	}
+	/// Lowers `min_len` to [`HashId::max_min_len`]
+	fn clamp_min_len(mut self) -> Self {
+		let max = self.max_min_len();
+		self.min_len = self.min_len.and_then(|len| NonZeroUsize::new(len.get().min(max)));
+		self
+	}
+	/// Caps the length of IDs, check character and MAC included.
+	///
+	/// Values whose ID would be longer encode to `None`, or an empty
+	/// string, and longer inputs fail to decode with
+	/// [`DecodeErr::Hash`](util::DecodeErr::Hash) before any work is done.
+	/// A `len` of 0 removes the cap.
+	///
+	/// ```rust
+	/// # use hashid_stack::prelude::*;
+	/// # fn main() {
+	/// let ids = HashIdB32::with_salt(b"1 2 3 4").with_max_len(6);
+	/// assert!(ids.encode_buf([1]).is_some());
+	/// assert!(ids.encode_buf([u64::MAX]).is_none());
+	/// # }
+	/// ```
+	pub fn with_max_len(mut self, len: usize) -> Self {
+		self.max_len = NonZeroUsize::new(len);
+		self
+	}
+	/// Maximum ID length, or 0 for none
+	pub fn max_len(&self) -> usize {
+		self.max_len.map_or(0, NonZeroUsize::get)
+	}
+	/// Whether an ID of `len` bytes is over the cap set by `with_max_len`
+	fn too_long(&self, len: usize) -> bool {
+		self.max_len.is_some_and(|max| len > max.get())
+	}
+	/// The part of the salt buffer in use
+	fn salt(&self) -> &[u8] {
+		unsafe { self.salt.get_unchecked(..self.salt_len) }
+	}
+	/// The tweak digest, or nothing outside of a tweaked call
+	fn tweak(&self) -> &[u8] {
+		self.tweak.as_ref().map_or(&[], |t| t)
+	}
+	/// Copy of this instance for one call with `tweak`
+	fn tweaked(&self, tweak: &[u8]) -> Self {
+		let digest = Sip::new(0, 0).write(TWEAK_LABEL).write(tweak).finish();
+		Self {
+			tweak: Some(digest.to_le_bytes()),
+			..*self
+		}
 	}
 	/// Extends a key to perform setup
 	fn extend_key(&self, lottery: u8) -> [u8; H::REAL - H::GUARDS] {
@@ -101,16 +485,17 @@ impl<
 		let mut tmp: [u8; H::REAL - H::GUARDS] = util::garbage();
 		tmp[0] = lottery;
 
-		// seed extension
-		for (v, s) in tmp.iter_mut().skip(1).zip(self.salt) {
-			*v = s;
+		// seed extension, with the tweak first so long salts can't push it out
+		let seed = self.tweak().iter().chain(self.salt()).chain(self.context());
+		for (v, s) in tmp.iter_mut().skip(1).zip(seed) {
+			*v = *s;
 		}
 
 		tmp
 	}
 	/// Reseeds key based on current alphabet state
 	fn reseed_key(&self, tmp: &mut [u8; H::REAL - H::GUARDS], alph: &[u8; H::REAL - H::GUARDS]) {
-		let alph_start = SALT + 1;
+		let alph_start = self.tweak().len() + self.salt_len + self.context().len() + 1;
 		for (v, s) in tmp.iter_mut().skip(alph_start).zip(alph) {
 			*v = *s;
 		}
@@ -146,33 +531,110 @@ impl<
 		#[cfg(not(feature = "smartstring"))]
 		let mut s = String::new();
 		if let Some(v) = self.encode_inner(values.as_ref()) {
-			let v = v.as_ref();
-			let v = unsafe { core::str::from_utf8_unchecked(v) };
-			s.push_str(v);
+			s.push_str(&v);
 		}
 		s
 	}
 
-	pub fn encode_buf(&self, values: impl AsRef<[u64]>) -> Option<ByteVec<BV_L_D>> {
+	/// Encode an ID list without allocating. `None` if there are no values
+	/// or the ID doesn't fit in a [`Buffer`].
+	pub fn encode_buf(&self, values: impl AsRef<[u64]>) -> Option<EncodedId> {
 		self.encode_inner(values.as_ref())
 	}
-	pub fn encode_inner(&self, values: &[u64]) -> Option<ByteVec<BV_L_D>> {
-		match values.as_ref() {
+	pub fn encode_inner(&self, values: &[u64]) -> Option<EncodedId> {
+		let nh = L::draw(self.salt(), values.iter().enumerate().map(|(i, &v)| self.permute(i, v)));
+		// offset the lottery per tweak, so tenants don't share first characters
+		let nh = nh.wrapping_add(self.tweak.map_or(0, u64::from_le_bytes));
+		self.encode_with(values, nh, nh)
+	}
+	/// Encodes an ID list with the lottery and guards drawn from `rng`.
+	///
+	/// The same values then give a different ID on each call, so IDs can't
+	/// be compared to tell whether they point at the same record. Only
+	/// instances built with [`HashId::randomized`] accept these IDs in
+	/// [`HashId::decode`]; [`HashId::decode_fast`] accepts them either way.
+	///
+	/// ```rust
+	/// # use hashid_stack::prelude::*;
+	/// # fn main() {
+	/// let ids = HashIdB64::with_salt_and_len(b"1 2 3 4", 8).randomized();
+	/// let mut seed = 0x2545f4914f6cdd1du64;
+	/// let mut rng = move || {
+	///     seed ^= seed << 13;
+	///     seed ^= seed >> 7;
+	///     seed ^= seed << 17;
+	///     seed
+	/// };
+	/// let a = ids.encode_random([42], &mut rng).unwrap();
+	/// let b = ids.encode_random([42], &mut rng).unwrap();
+	/// assert_ne!(a, b);
+	/// assert_eq!([42], ids.decode(a).unwrap());
+	/// assert_eq!([42], ids.decode(b).unwrap());
+	/// # }
+	/// ```
+	pub fn encode_random(
+		&self,
+		values: impl AsRef<[u64]>,
+		mut rng: impl FnMut() -> u64,
+	) -> Option<EncodedId> {
+		let lottery = rng();
+		self.encode_with(values.as_ref(), lottery, rng())
+	}
+	/// Encodes an ID list under a per-call tweak, such as a tenant ID.
+	///
+	/// The tweak is hashed and goes into the lottery and the key for every
+	/// value, ahead of the salt, so the same values encode differently for
+	/// each tweak. Decode
+	/// with [`HashId::decode_with_tweak`] and the same tweak; like with
+	/// [`HashId::with_context`], other tweaks read it as some other number
+	/// if at all, unless a MAC rejects it; the tweak also goes into the tag.
+	///
+	/// ```rust
+	/// # use hashid_stack::prelude::*;
+	/// # fn main() {
+	/// let ids = HashIdB64::with_salt(b"1 2 3 4");
+	/// let id = ids.encode_with_tweak([42], b"tenant 1").unwrap();
+	/// assert_ne!(id, ids.encode_with_tweak([42], b"tenant 2").unwrap());
+	/// assert_eq!([42], ids.decode_with_tweak(&id, b"tenant 1").unwrap());
+	/// assert!(ids.decode_with_tweak::<1>(&id, b"tenant 2").map_or(true, |v| v != [42]));
+	/// # }
+	/// ```
+	pub fn encode_with_tweak(&self, values: impl AsRef<[u64]>, tweak: &[u8]) -> Option<EncodedId> {
+		self.tweaked(tweak).encode_inner(values.as_ref())
+	}
+	/// Decodes an ID from [`HashId::encode_with_tweak`] under the same tweak.
+	pub fn decode_with_tweak<const OUT: usize>(
+		&self,
+		input: impl AsRef<[u8]>,
+		tweak: &[u8],
+	) -> Result<[u64; OUT], util::DecodeErr<OUT>> {
+		self.tweaked(tweak).decode(input)
+	}
+	/// Makes [`HashId::decode`] accept IDs from [`HashId::encode_random`].
+	///
+	/// Decoding still re-encodes and compares, but with the lottery taken
+	/// from the input and any guard accepted in guard positions.
+	pub fn randomized(mut self) -> Self {
+		self.random = true;
+		self
+	}
+	/// Encodes with the lottery picked by `nh` and guards picked by `gh`
+	fn encode_with(&self, values: &[u64], nh: u64, gh: u64) -> Option<EncodedId> {
+		match values {
 			| [] => None,
 			| values => {
-				let nh = util::make_nhash(values);
 				let mut buffer = ByteVec::new();
 
 				let i = nh as usize % (H::REAL - H::GUARDS);
 				let lottery = *unsafe { self.alphabet.get_unchecked(i) };
 				buffer.push(lottery);
 				let mut tmp = self.extend_key(lottery);
-				let mut alph = self.alphabet.clone();
+				let mut alph = self.alphabet;
 
 				for (i, &val) in values.iter().enumerate() {
-					let mut val = val;
+					let mut val = self.permute(i, val);
 					self.reseed_key(&mut tmp, &alph);
-					util::shuffle(&mut alph, &tmp);
+					S::shuffle(&mut alph, &tmp);
 					let last = util::make_hash_fast(val, alph);
 					buffer.extend(&last.0[last.1..]);
 					if i + 1 < values.len() {
@@ -185,36 +647,44 @@ impl<
 				if let Some(len) = self.min_len.map(NonZeroUsize::get) {
 					// Extension round 1
 					if buffer.len() < len {
-						let g_idx = nh as usize + unsafe { buffer.get(0) } as usize;
+						let g_idx = gh as usize + unsafe { buffer.get(0) } as usize;
 						let guard = *unsafe { self.guards.get_unchecked(g_idx % H::GUARDS) };
 						buffer.insert(0, guard);
 
 						// Extension round 2
 						if buffer.len() < len {
-							let g_idx = nh as usize + unsafe { buffer.get(2) } as usize;
+							let g_idx = gh as usize + unsafe { buffer.get(2) } as usize;
 							let guard = *unsafe { self.guards.get_unchecked(g_idx % H::GUARDS) };
 							buffer.push(guard);
 						}
 					}
 					let mid = (H::REAL - H::GUARDS) / 2;
 					while buffer.len() < len {
+						// wrap in the alphabet halves, keeping at most the middle `len`
 						let (l, r) = alph.split_at(mid);
+						let excess = (r.len() + buffer.len() + l.len()).saturating_sub(len);
 						buffer = [r, buffer.as_ref(), l]
 							.into_iter()
 							.flatten()
+							.skip(excess / 2)
+							.take(len)
 							.copied()
 							.collect();
-
-						let excess = buffer.len() - len;
-						if excess > 0 {
-							let marker = excess / 2;
-							let buf = &buffer.as_ref()[marker..marker + len];
-							buffer = buf.into_iter().copied().collect();
-						}
 					}
 				}
+				if let Some(mac) = &self.mac {
+					buffer.extend(&self.mac_tag(mac, values)[..mac.len]);
+				}
+				if self.check {
+					if let Some(c) = Self::check_char(buffer.as_ref()) {
+						buffer.push(c);
+					}
+				}
+				if buffer.overflowed() || self.too_long(buffer.len()) {
+					return None;
+				}
 
-				Some(buffer)
+				Some(EncodedId::new(buffer))
 			}
 		}
 	}
@@ -231,24 +701,226 @@ impl<
 		input: impl AsRef<[u8]>,
 	) -> Result<[u64; OUT], util::DecodeErr<OUT>> {
 		let input = input.as_ref();
-		let out = self.decode_fast(input)?;
-		let encode = self.encode_buf(out);
-		if let Some(encoded) = encode {
-			if encoded.as_ref() == input {
-				return Ok(out);
-			}
+		let (out, lottery) = self.decode_parts(input)?;
+		match self.is_canonical(input, &out, lottery) {
+			| true => Ok(out),
+			| false => Err(util::DecodeErr::Hash),
 		}
-		Err(util::DecodeErr::Hash)
+	}
+	/// Same as [`HashId::decode`], for a number of values only known at
+	/// runtime. Fails unless `input` holds exactly `out.len()` values.
+	///
+	/// ```rust
+	/// # use hashid_stack::prelude::*;
+	/// # fn main() {
+	/// let ids = HashIdB64::with_salt(b"1 2 3 4");
+	/// let mut out = vec![0; 3];
+	/// ids.decode_into(ids.encode([1, 2, 3]), &mut out).unwrap();
+	/// assert_eq!([1, 2, 3], out[..]);
+	/// # }
+	/// ```
+	pub fn decode_into(
+		&self,
+		input: impl AsRef<[u8]>,
+		out: &mut [u64],
+	) -> Result<(), util::DecodeErr<0>> {
+		let input = input.as_ref();
+		let lottery = self.decode_parts_into(input, out)?;
+		match self.is_canonical(input, out, lottery) {
+			| true => Ok(()),
+			| false => Err(util::DecodeErr::Hash),
+		}
+	}
+	/// Whether `input` is what encoding `out` gives, as `decode` requires
+	fn is_canonical(&self, input: &[u8], out: &[u64], lottery: u8) -> bool {
+		if !self.random {
+			return self.encode_inner(out).is_some_and(|encoded| encoded.as_bytes() == input);
+		}
+		let nh = self.alphabet.iter().position(|&c| c == lottery);
+		nh.and_then(|nh| self.encode_with(out, nh as u64, 0))
+			.is_some_and(|encoded| self.same_but_guards(encoded.as_bytes(), input))
+	}
+	/// Compares IDs, allowing any guard where the other has one. The check
+	/// character depends on the guards, so it's left to `decode_parts`.
+	fn same_but_guards(&self, a: &[u8], b: &[u8]) -> bool {
+		let len = a.len() - self.check as usize;
+		a.len() == b.len()
+			&& a[..len].iter().zip(b).all(|(x, y)| {
+				x == y || (self.guards.contains(x) && self.guards.contains(y))
+			})
+	}
+
+	/// Encodes values together with the time they were issued at.
+	///
+	/// The timestamp is encoded as one more value after `values`, in whatever
+	/// unit the caller uses for [`HashId::decode_expiring`]. Without
+	/// [`HashId::with_mac`] it is only as tamper-proof as the salt.
+	///
+	/// ```rust
+	/// # use hashid_stack::prelude::*;
+	/// # fn main() {
+	/// let ids = HashIdB64::with_salt(b"1 2 3 4");
+	/// let link = ids.encode_expiring([42], 1_700_000_000).unwrap();
+	///
+	/// let (values, issued) = ids.decode_expiring::<1>(&link, 1_700_000_600, 3600, 60).unwrap();
+	/// assert_eq!(([42], 1_700_000_000), (values, issued));
+	/// assert!(matches!(
+	///     ids.decode_expiring::<1>(&link, 1_700_003_601, 3600, 60),
+	///     Err(DecodeErr::Expired(1_700_000_000))
+	/// ));
+	/// # }
+	/// ```
+	pub fn encode_expiring<const N: usize>(&self, values: [u64; N], issued_at: u64) -> Option<EncodedId>
+	where
+		[(); N + 1]: Sized,
+	{
+		let mut all = [issued_at; N + 1];
+		all[..N].copy_from_slice(&values);
+		self.encode_inner(&all)
+	}
+	/// Decodes an ID from [`HashId::encode_expiring`], rejecting it once `ttl`
+	/// has passed since it was issued.
+	///
+	/// `now`, `ttl` and `max_skew` are in the unit used when encoding. IDs
+	/// issued up to `max_skew` after `now` are accepted, to allow for clock
+	/// skew between services; later ones fail with
+	/// [`DecodeErr::Future`](util::DecodeErr::Future) so they can't outlive
+	/// the TTL. On success the issue time is returned next to the values.
+	pub fn decode_expiring<const OUT: usize>(
+		&self,
+		input: impl AsRef<[u8]>,
+		now: u64,
+		ttl: u64,
+		max_skew: u64,
+	) -> Result<([u64; OUT], u64), util::DecodeErr<OUT>>
+	where
+		[(); OUT + 1]: Sized,
+	{
+		let all = self.decode::<{ OUT + 1 }>(input).map_err(util::DecodeErr::resize)?;
+		let issued_at = all[OUT];
+		if issued_at > now.saturating_add(max_skew) {
+			return Err(util::DecodeErr::Future(issued_at));
+		}
+		if now.saturating_sub(issued_at) > ttl {
+			return Err(util::DecodeErr::Expired(issued_at));
+		}
+		let mut out = [0; OUT];
+		out.copy_from_slice(&all[..OUT]);
+		Ok((out, issued_at))
+	}
+
+	/// Suggests valid IDs within one edit of `input`.
+	///
+	/// Tries every swap of neighbouring characters, every substitution,
+	/// deletion and insertion of a character from the variant's alphabet, in
+	/// that order, and yields the candidates [`HashId::decode`] accepts with
+	/// `OUT` values. Nothing is allocated, and at most
+	/// `(2 * input.len() + 1) * H::ALPH.len() + 2 * input.len()` candidates are
+	/// checked. Inputs too long for [`Buffer`] yield nothing.
+	///
+	/// ```rust
+	/// # use hashid_stack::prelude::*;
+	/// # fn main() {
+	/// let ids = HashIdB32::with_salt(b"1 2 3 4").with_check_char();
+	/// let id = ids.encode([1234]);
+	/// let typo = format!("{}Z{}", &id[..2], &id[3..]);
+	/// assert!(ids.suggest::<1>(&typo).any(|s| s == *id));
+	/// # }
+	/// ```
+	pub fn suggest<const OUT: usize>(
+		&self,
+		input: impl AsRef<[u8]>,
+	) -> impl Iterator<Item = EncodedId> + '_ {
+		let input = input.as_ref();
+		let input: Buffer = match input.len() + 1 < BV_L_D {
+			| true => input.iter().copied().collect(),
+			| false => ByteVec::new(),
+		};
+		let n = input.len();
+		let at = move |i: usize| unsafe { input.get(i) };
+
+		let swaps = (1..n).filter(move |&i| at(i - 1) != at(i)).map(move |i| {
+			let mut out = input;
+			out.as_mut().swap(i - 1, i);
+			out
+		});
+		let subs = (0..n).flat_map(move |i| {
+			H::ALPH.iter().filter(move |&&c| c != at(i)).map(move |&c| {
+				let mut out = input;
+				out.as_mut()[i] = c;
+				out
+			})
+		});
+		let dels = (0..n).filter(move |&i| i == 0 || at(i - 1) != at(i)).map(move |i| {
+			let (l, r) = input.as_ref().split_at(i);
+			l.iter().chain(&r[1..]).copied().collect()
+		});
+		let ins = (0..=n).flat_map(move |i| {
+			H::ALPH.iter().filter(move |&&c| i == 0 || c != at(i - 1)).map(move |&c| {
+				let mut out = input;
+				out.insert(i, c);
+				out
+			})
+		});
+		swaps
+			.chain(subs)
+			.chain(dels)
+			.chain(ins)
+			.filter(move |candidate| self.decode::<OUT>(candidate).is_ok())
+			// decode only accepts what the encoder writes
+			.map(EncodedId::new)
 	}
 
 	pub fn decode_fast<const OUT: usize>(
 		&self,
 		input: impl AsRef<[u8]>,
 	) -> Result<[u64; OUT], util::DecodeErr<OUT>> {
-		self.decode_inner(input.as_ref())
+		self.decode_parts(input.as_ref()).map(|(out, _)| out)
+	}
+	/// Checks and strips the check character and MAC, then decodes the
+	/// values along with the lottery character.
+	fn decode_parts<const OUT: usize>(
+		&self,
+		input: &[u8],
+	) -> Result<([u64; OUT], u8), util::DecodeErr<OUT>> {
+		let mut out = [0; OUT];
+		match self.decode_parts_into(input, &mut out) {
+			| Ok(lottery) => Ok((out, lottery)),
+			| Err(e) => Err(e.with_values(out)),
+		}
 	}
-	fn decode_inner<const OUT: usize>(&self, input: &[u8]) -> Result<[u64; OUT], util::DecodeErr<OUT>> {
-		let mut val = input.as_ref();
+	/// Slice backed [`HashId::decode_parts`], filling `out`
+	fn decode_parts_into(
+		&self,
+		mut input: &[u8],
+		out: &mut [u64],
+	) -> Result<u8, util::DecodeErr<0>> {
+		if self.too_long(input.len()) {
+			return Err(util::DecodeErr::Hash);
+		}
+		if self.check {
+			let (&c, body) = input.split_last().ok_or(util::DecodeErr::Check)?;
+			if Self::check_char(body) != Some(c) {
+				return Err(util::DecodeErr::Check);
+			}
+			input = body;
+		}
+		match &self.mac {
+			| None => self.decode_inner(input, out),
+			| Some(mac) => {
+				let split = input.len().checked_sub(mac.len).ok_or(util::DecodeErr::Mac)?;
+				let (input, tag) = input.split_at(split);
+				let lottery = self.decode_inner(input, out)?;
+				if util::ct_eq(&self.mac_tag(mac, out)[..mac.len], tag) {
+					Ok(lottery)
+				} else {
+					Err(util::DecodeErr::Mac)
+				}
+			}
+		}
+	}
+	fn decode_inner(&self, input: &[u8], out: &mut [u64]) -> Result<u8, util::DecodeErr<0>> {
+		let mut val = input;
 		if let Some(g_idx) = val.iter().position(|u| self.guards.contains(u)) {
 			val = &val[(g_idx + 1)..];
 		}
@@ -258,18 +930,17 @@ impl<
 		if val.len() < 2 {
 			Err(util::DecodeErr::Hash)
 		} else {
-			let mut alph = self.alphabet.clone();
+			let mut alph = self.alphabet;
 			match val.split_first() {
 				| None => unsafe { unreachable_unchecked() },
 				| Some((&lottery, val)) => {
 					let mut tmp = self.extend_key(lottery);
 					let segs = val.split(|u| self.separators.contains(u));
-					let result = segs.map(|seg| {
+					let result = segs.enumerate().map(|(i, seg)| {
 						self.reseed_key(&mut tmp, &alph);
-						util::shuffle(&mut alph, &tmp);
-						util::unhash(seg, alph)
+						S::shuffle(&mut alph, &tmp);
+						util::unhash(seg, alph).map(|v| self.unpermute(i, v))
 					});
-					let mut out = [0; OUT];
 					let mut max = 0;
 					for  val in result {
 						if let Some(val) = val {
@@ -278,12 +949,12 @@ impl<
 							}
 							max += 1;
 						} else {
-							return Err(util::DecodeErr::Value(max, out));
+							return Err(util::DecodeErr::Value(max, []));
 						}
 					}
-					match max.cmp(&OUT) {
-						| Ordering::Equal => Ok(out),
-						| _ => Err(util::DecodeErr::Items(OUT, max)),
+					match max.cmp(&out.len()) {
+						| Ordering::Equal => Ok(lottery),
+						| _ => Err(util::DecodeErr::Items(out.len(), max)),
 					}
 				}
 			}
@@ -301,3 +972,185 @@ where
 		H::with_salt(b"")
 	}
 }
+
+impl<H: Hash, const SALT: usize, L: Lottery, S: Shuffle> HashId<H, SALT, L, S>
+where
+	[(); H::SEP.len()]: Sized,
+	[(); H::REAL - H::GUARDS]: Sized,
+{
+	/// Short keyed digest of the settings that decide which IDs are
+	/// produced, for comparing or logging at startup to catch services that
+	/// disagree.
+	///
+	/// Covers the variant, lottery and shuffle, the shuffled alphabet,
+	/// separators and guards, the length limits, whether a check character,
+	/// MAC or FPE is used, and whether randomized IDs are accepted. MAC and
+	/// FPE keys are not hashed in.
+	///
+	/// The alphabet follows from the salt, so an unkeyed digest would let
+	/// anyone holding a fingerprint check salt guesses offline. `key` must
+	/// be kept as secret as the salt; fingerprints are only comparable under
+	/// the same key.
+	///
+	/// ```rust
+	/// # use hashid_stack::prelude::*;
+	/// # fn main() {
+	/// let key = b"fingerprint key!";
+	/// let ids = HashIdB64::with_salt(b"1 2 3 4");
+	/// assert_eq!(ids.fingerprint(key), HashIdB64::with_salt(b"1 2 3 4").fingerprint(key));
+	/// assert_ne!(ids.fingerprint(key), HashIdB64::with_salt(b"4 3 2 1").fingerprint(key));
+	/// assert_ne!(ids.fingerprint(key), ids.with_check_char().fingerprint(key));
+	/// # }
+	/// ```
+	pub fn fingerprint(&self, key: &[u8; 16]) -> [u8; 8] {
+		let mut sip = Sip::with_key(key);
+		sip.write(FINGERPRINT_LABEL);
+		for name in [H::NAME, L::NAME, S::NAME] {
+			sip.write_u64(name.len() as u64).write(name.as_bytes());
+		}
+		sip.write(&self.alphabet).write(&self.separators).write(&self.guards);
+		let len = |len: Option<NonZeroUsize>| len.map_or(0, NonZeroUsize::get) as u64;
+		sip.write_u64(len(self.min_len)).write_u64(len(self.max_len));
+		sip.write_u64(self.mac.map_or(0, |mac| mac.len) as u64);
+		sip.write(&[self.check as u8, self.fpe.is_some() as u8, self.random as u8]);
+		sip.finish().to_be_bytes()
+	}
+	/// [`HashId::fingerprint`] as 16 lowercase hex digits
+	#[cfg(feature = "std")]
+	pub fn fingerprint_hex(&self, key: &[u8; 16]) -> String {
+		const HEX: &[u8; 16] = b"0123456789abcdef";
+		let mut s = String::new();
+		for b in self.fingerprint(key) {
+			s.push(HEX[(b >> 4) as usize] as char);
+			s.push(HEX[(b & 15) as usize] as char);
+		}
+		s
+	}
+}
+
+impl<H: Hash, const SALT: usize, L: Lottery, S: Shuffle> fmt::Debug for HashId<H, SALT, L, S>
+where
+	[(); H::SEP.len()]: Sized,
+	[(); H::REAL - H::GUARDS]: Sized,
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		/// Stands in for secret fields
+		struct Redacted(usize);
+		impl fmt::Debug for Redacted {
+			fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				write!(f, "<redacted, {} bytes>", self.0)
+			}
+		}
+		f.debug_struct("HashId")
+			.field("variant", &core::any::type_name::<H>())
+			.field("lottery", &core::any::type_name::<L>())
+			.field("shuffle", &core::any::type_name::<S>())
+			.field("salt", &Redacted(self.salt_len))
+			.field("min_len", &self.min_len)
+			.field("max_len", &self.max_len)
+			.field("mac_len", &self.mac.map(|mac| mac.len))
+			.field("check", &self.check)
+			.field("fpe", &self.fpe.is_some())
+			.field("random", &self.random)
+			.field("context", &self.context.is_some())
+			.field("tweak", &self.tweak.is_some())
+			.finish_non_exhaustive()
+	}
+}
+
+/// Equal when every setting and secret matches, so both produce and accept
+/// the same IDs.
+impl<H: Hash, const SALT: usize, L: Lottery, S: Shuffle> PartialEq for HashId<H, SALT, L, S>
+where
+	[(); H::SEP.len()]: Sized,
+	[(); H::REAL - H::GUARDS]: Sized,
+{
+	fn eq(&self, other: &Self) -> bool {
+		self.salt[..self.salt_len] == other.salt[..other.salt_len]
+			&& self.min_len == other.min_len
+			&& self.max_len == other.max_len
+			&& self.alphabet == other.alphabet
+			&& self.separators == other.separators
+			&& self.guards == other.guards
+			&& self.mac == other.mac
+			&& self.check == other.check
+			&& self.fpe.map(|fpe| fpe.key) == other.fpe.map(|fpe| fpe.key)
+			&& self.random == other.random
+			&& self.context == other.context
+			&& self.tweak == other.tweak
+	}
+}
+
+impl<H: Hash, const SALT: usize, L: Lottery, S: Shuffle> Eq for HashId<H, SALT, L, S>
+where
+	[(); H::SEP.len()]: Sized,
+	[(); H::REAL - H::GUARDS]: Sized,
+{
+}
+
+/// Hashes the settings [`HashId::fingerprint`] covers, which equal instances
+/// share.
+impl<H: Hash, const SALT: usize, L: Lottery, S: Shuffle> core::hash::Hash for HashId<H, SALT, L, S>
+where
+	[(); H::SEP.len()]: Sized,
+	[(); H::REAL - H::GUARDS]: Sized,
+{
+	fn hash<St: core::hash::Hasher>(&self, state: &mut St) {
+		self.alphabet.hash(state);
+		self.separators.hash(state);
+		self.guards.hash(state);
+		self.min_len.hash(state);
+		self.max_len.hash(state);
+		self.mac.map(|mac| mac.len).hash(state);
+		(self.check, self.fpe.is_some(), self.random).hash(state);
+		self.context.hash(state);
+	}
+}
+
+#[cfg(feature = "zeroize")]
+impl<H: Hash, const SALT: usize, L: Lottery, S: Shuffle> zeroize::Zeroize
+	for HashId<H, SALT, L, S>
+where
+	[(); H::SEP.len()]: Sized,
+	[(); H::REAL - H::GUARDS]: Sized,
+{
+	fn zeroize(&mut self) {
+		self.salt.zeroize();
+		self.salt_len.zeroize();
+		self.alphabet.zeroize();
+		self.separators.zeroize();
+		self.guards.zeroize();
+		if let Some(mac) = &mut self.mac {
+			mac.key.zeroize();
+		}
+		if let Some(fpe) = &mut self.fpe {
+			fpe.key.zeroize();
+		}
+		self.context.zeroize();
+		self.tweak.zeroize();
+	}
+}
+
+#[cfg(all(test, feature = "zeroize"))]
+mod tests {
+	use crate::variants::{HashId as _, HashIdB64};
+	use zeroize::Zeroize;
+	#[test]
+	fn zeroize_wipes_every_secret() {
+		let mut hi = HashIdB64::with_salt(b"super secret salt")
+			.with_mac(*b"sixteen byte key", 4)
+			.with_fpe()
+			.with_context(b"user")
+			.tweaked(b"tenant");
+		hi.zeroize();
+		assert_eq!([0; 17], hi.salt);
+		assert_eq!(0, hi.salt_len);
+		assert!(hi.alphabet.iter().all(|&b| b == 0));
+		assert!(hi.separators.iter().all(|&b| b == 0));
+		assert!(hi.guards.iter().all(|&b| b == 0));
+		assert_eq!(Some([0; 16]), hi.mac.map(|mac| mac.key));
+		assert_eq!(Some([0; 2]), hi.fpe.map(|fpe| fpe.key));
+		assert_eq!(None, hi.context);
+		assert_eq!(None, hi.tweak);
+	}
+}
@@ -0,0 +1,48 @@
+//! Passphrase to salt derivation.
+//!
+//! Operators tend to hand out human passphrases, while the encoders want raw
+//! salt bytes. The derivation here is deliberately simple so it can be
+//! reproduced in any language:
+//!
+//! ```text
+//! salt = SHA-256(CONTEXT || 0x00 || passphrase)
+//! ```
+//!
+//! The same passphrase always yields the same 32 byte salt, and therefore the
+//! same encoder, in every service.
+//!
+//! This is not a password hash. It does nothing to slow down guessing, so use
+//! a passphrase with enough entropy to stand on its own.
+use sha2::{Digest, Sha256};
+
+/// Context string prefixed to every passphrase before hashing.
+pub const CONTEXT: &[u8] = b"hashid-stack passphrase salt v1";
+
+/// Length of a derived salt
+pub const SALT_LEN: usize = 32;
+
+/// A derived salt
+pub type Salt = [u8; SALT_LEN];
+
+/// Hash ID built from a derived salt
+pub type PassphraseHashId<H> = crate::hash::HashId<H, SALT_LEN>;
+
+/// Derives a fixed-size salt from a passphrase.
+///
+/// ```rust
+/// # use hashid_stack::{kdf, prelude::*};
+/// # fn main() {
+/// let salt = kdf::derive_salt("correct horse battery staple");
+/// let a = HashIdB64::with_salt(&salt);
+/// let b = HashIdB64::with_passphrase("correct horse battery staple");
+/// assert_eq!(a.encode([1, 2, 3]), b.encode([1, 2, 3]));
+/// # }
+/// ```
+pub fn derive_salt(passphrase: impl AsRef<[u8]>) -> Salt {
+	Sha256::new()
+		.chain_update(CONTEXT)
+		.chain_update([0])
+		.chain_update(passphrase)
+		.finalize()
+		.into()
+}
@@ -0,0 +1,136 @@
+//! Salt rotation: encode with the primary configuration, decode with any.
+use crate::{
+	encoded::EncodedId,
+	hash::HashId,
+	lottery::{Classic, Lottery},
+	shuffle::{Consistent, Shuffle},
+	util,
+	variants::HashId as Hash,
+};
+
+#[cfg(all(feature = "smartstring", feature = "std"))]
+use smartstring::alias::String;
+
+/// Ordered set of hash ID configurations, used for rotating salts.
+///
+/// New IDs are always encoded with the primary configuration. Decoding tries
+/// the primary first, then each older configuration in order, and reports the
+/// index of the one that accepted the input: `0` for the primary, `1` for the
+/// first older configuration, and so on.
+///
+/// Salts alone don't keep configurations apart: a plain ID is canonical
+/// under an unrelated salt surprisingly often, about 1.7% of single-value
+/// [`HashIdB64`](crate::variants::HashIdB64) IDs and 0.8% with a `min_len`
+/// of 8. Give every configuration after the first its own
+/// [`HashId::with_mac`] key, so IDs of other configurations fail its tag,
+/// or tag IDs with [`Versioned`](crate::versioned::Versioned) instead.
+/// Older configurations may still accept newer IDs, so a match under a MAC
+/// wins; inputs that more than one configuration without a MAC accepts
+/// fail with [`DecodeErr::Ambiguous`](util::DecodeErr::Ambiguous) rather
+/// than decoding to the wrong record.
+///
+/// ```rust
+/// # use hashid_stack::prelude::*;
+/// # fn main() {
+/// let old = HashIdB64::with_salt(b"old salt");
+/// let new = HashIdB64::with_salt(b"new salt").with_mac(*b"generation 2 key", 4);
+/// let ring = KeyRing::new(new, [old]);
+///
+/// let (values, key) = ring.decode::<2>(old.encode([1, 2])).unwrap();
+/// assert_eq!(([1, 2], 1), (values, key));
+/// let (values, key) = ring.decode::<2>(ring.encode([1, 2])).unwrap();
+/// assert_eq!(([1, 2], 0), (values, key));
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct KeyRing<
+	H: Hash,
+	const SALT: usize,
+	const N: usize,
+	L: Lottery = Classic,
+	S: Shuffle = Consistent,
+> where
+	[(); H::SEP.len()]: Sized,
+	[(); H::REAL - H::GUARDS]: Sized,
+{
+	primary: HashId<H, SALT, L, S>,
+	older: [HashId<H, SALT, L, S>; N],
+}
+
+impl<H: Hash, const SALT: usize, const N: usize, L: Lottery, S: Shuffle> KeyRing<H, SALT, N, L, S>
+where
+	[(); H::SEP.len()]: Sized,
+	[(); H::REAL - H::GUARDS]: Sized,
+	[(); H::ALPH.len()]: Sized,
+	[(); H::REAL]: Sized,
+{
+	/// Creates a key ring from the current configuration and the older ones
+	/// still accepted on decode, newest first.
+	pub fn new(primary: HashId<H, SALT, L, S>, older: [HashId<H, SALT, L, S>; N]) -> Self {
+		Self { primary, older }
+	}
+	/// The configuration used for encoding
+	pub fn primary(&self) -> &HashId<H, SALT, L, S> {
+		&self.primary
+	}
+	/// Configuration at the given index, as reported by [`KeyRing::decode`]
+	pub fn get(&self, idx: usize) -> Option<&HashId<H, SALT, L, S>> {
+		match idx {
+			| 0 => Some(&self.primary),
+			| idx => self.older.get(idx - 1),
+		}
+	}
+	/// Number of configurations, including the primary
+	#[allow(clippy::len_without_is_empty)]
+	pub fn len(&self) -> usize {
+		N + 1
+	}
+
+	/// Encode an ID list with the primary configuration
+	#[cfg(feature = "std")]
+	pub fn encode(&self, values: impl AsRef<[u64]>) -> String {
+		self.primary.encode(values)
+	}
+	/// Encode an ID list with the primary configuration
+	pub fn encode_buf(&self, values: impl AsRef<[u64]>) -> Option<EncodedId> {
+		self.primary.encode_buf(values)
+	}
+
+	/// Decodes a value, returning it with the index of the matching configuration.
+	///
+	/// Every configuration uses the strict [`HashId::decode`], in order. The
+	/// first one with a MAC to accept the input decides. Otherwise all of
+	/// them are tried, and the input must be accepted by exactly one: if
+	/// none do, the error from the primary configuration is returned, and
+	/// if several do, [`DecodeErr::Ambiguous`](util::DecodeErr::Ambiguous).
+	pub fn decode<const OUT: usize>(
+		&self,
+		input: impl AsRef<[u8]>,
+	) -> Result<([u64; OUT], usize), util::DecodeErr<OUT>> {
+		let input = input.as_ref();
+		let mut found: Result<_, util::DecodeErr<OUT>> = Err(util::DecodeErr::Hash);
+		for (idx, key) in core::iter::once(&self.primary).chain(&self.older).enumerate() {
+			match (key.decode(input), &found) {
+				| (Ok(out), _) if key.mac_len() > 0 => return Ok((out, idx)),
+				| (Ok(_), Ok((_, first))) => return Err(util::DecodeErr::Ambiguous(*first, idx)),
+				| (Ok(out), Err(_)) => found = Ok((out, idx)),
+				| (Err(err), _) if idx == 0 => found = Err(err),
+				| (Err(_), _) => {}
+			}
+		}
+		found
+	}
+}
+
+#[cfg(feature = "zeroize")]
+impl<H: Hash, const SALT: usize, const N: usize, L: Lottery, S: Shuffle> zeroize::Zeroize
+	for KeyRing<H, SALT, N, L, S>
+where
+	[(); H::SEP.len()]: Sized,
+	[(); H::REAL - H::GUARDS]: Sized,
+{
+	fn zeroize(&mut self) {
+		self.primary.zeroize();
+		self.older.iter_mut().for_each(zeroize::Zeroize::zeroize);
+	}
+}
@@ -6,8 +6,6 @@
 #![feature(slice_swap_unchecked)]
 // used in hash::HashId, variants::HashId extensively
 #![feature(generic_const_exprs)]
-// used in variants::HashId for guard info.
-#![feature(int_roundings)]
 // used in bytevec::ByteVec as core::iter::Extend
 //#![feature(extend_one)]
 // used in hash::HashIds::set_bv_len
@@ -32,10 +30,10 @@
 //!
 //! - Currently requires nightly compiler due to use of
 //!     - `generic_const_exprs` for most functionality
-//!     - `int_roundings` for guard use
 //!     - `slice_swap_unchecked` for `util::shuffle`
 //! - Less tested
-//! - All inbound data must be known at compile time, including length of the salt.
+//! - All inbound data must be known at compile time, including length of the salt,
+//!   unless using the bounded [`hash::BoundedHashId`] (up to [`hash::MAX_SALT`] bytes).
 //! - The return values are an array that have some custom formatting functions added, or a [`SmartString<LazyCompact>`](https://docs.rs/smartstring/latest/smartstring/alias/type.String.html).
 //!
 //! This does come with some benefits, though
@@ -44,17 +42,39 @@
 //! - Little to no requirement for allocation
 //!
 
+pub mod any;
+pub mod codec;
+#[cfg(feature = "config")]
+pub mod config;
+pub mod encoded;
+mod fpe;
 pub mod hash;
+#[cfg(feature = "kdf")]
+pub mod kdf;
+pub mod keyring;
+pub mod lottery;
+mod macros;
+pub mod prefixed;
+#[cfg(feature = "std")]
+pub mod registry;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod shuffle;
+mod sip;
+pub mod transcode;
 mod util;
 pub mod variants;
+pub mod versioned;
 
 pub mod prelude {
+	pub use crate::encoded::EncodedId;
 	pub use crate::util::DecodeErr;
 	pub use crate::variants::{
 		HashId as _, HashIdB32 as B32, HashIdB64 as B64, HashIdDefault as HashIds,
 		HashIdDefault as Normal, HashIdQr as QR, *,
 	};
-	pub use crate::hash::HashId as HashID;
+	pub use crate::hash::{BoundedHashId, HashId as HashID};
+	pub use crate::keyring::KeyRing;
 }
 
 /// Simple `Copy` byte vector. Has display.
@@ -0,0 +1,58 @@
+//! Strategies for picking the lottery character of an ID.
+//!
+//! The lottery is the first character of every ID. It selects how the
+//! alphabet gets shuffled for the rest of it, so it is embedded in the ID and
+//! decoding never needs to recompute it. Only encoding, and the strict
+//! re-encode check in `HashId::decode`, depend on the strategy.
+use crate::{sip::Sip, util};
+
+/// Picks the lottery for a list of values.
+///
+/// `draw` returns a number which is reduced modulo the alphabet size to pick
+/// the lottery character, and also used to pick guards when padding to
+/// `min_len`. Implementations must be deterministic.
+pub trait Lottery: Copy {
+	/// Stable name, hashed into [`HashId::fingerprint`](crate::hash::HashId::fingerprint)
+	const NAME: &'static str;
+	fn draw(salt: &[u8], values: impl Iterator<Item = u64>) -> u64;
+}
+
+/// The hashids lottery, summing `value % (index + 100)`.
+///
+/// This is the default and matches the output of other hashids
+/// implementations. Small or sequential values cluster on a few lottery
+/// characters, so consecutive IDs often share their first character.
+#[derive(Clone, Copy, Debug)]
+pub struct Classic;
+impl Lottery for Classic {
+	const NAME: &'static str = "classic";
+	fn draw(_: &[u8], values: impl Iterator<Item = u64>) -> u64 {
+		util::make_nhash(values)
+	}
+}
+
+/// Keyed lottery, SipHash-2-4 over the values with a key derived from the salt.
+///
+/// Spreads lotteries uniformly over the alphabet regardless of how values
+/// are distributed. IDs differ from [`Classic`] ones, but decode the same.
+///
+/// ```rust
+/// # use hashid_stack::{lottery::Keyed, prelude::*};
+/// # fn main() {
+/// let ids = HashIdB64::with_salt(b"1 2 3 4").with_lottery::<Keyed>();
+/// assert_eq!([5], ids.decode(ids.encode([5])).unwrap());
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Keyed;
+impl Lottery for Keyed {
+	const NAME: &'static str = "keyed";
+	fn draw(salt: &[u8], values: impl Iterator<Item = u64>) -> u64 {
+		let key = |label: &[u8]| Sip::new(0, 0).write(label).write(salt).finish();
+		let mut sip = Sip::new(key(b"hashid-stack lottery k0"), key(b"hashid-stack lottery k1"));
+		for value in values {
+			sip.write_u64(value);
+		}
+		sip.finish()
+	}
+}
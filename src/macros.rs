@@ -0,0 +1,132 @@
+/// Defines a `u64` newtype that is written and parsed as a hash ID.
+///
+/// The type gets `Display` through the encoder, `FromStr` and
+/// `TryFrom<&str>` through strict [`decode`](crate::hash::HashId::decode),
+/// conversions to and from `u64`, and `Copy`, `Eq`, `Ord` and `Hash` on
+/// the number. The encoder is a [`BoundedHashId`](crate::hash::BoundedHashId)
+/// built on first use and kept in a `static`, see `encoder()`. Salts longer
+/// than [`MAX_SALT`](crate::hash::MAX_SALT) panic there.
+///
+/// With the `serde` feature the type also serializes as its ID, and
+/// implements `serde::Source` so plain `u64` fields can use it through
+/// `serde::As`.
+///
+/// ```rust
+/// # use hashid_stack::{hashid_type, prelude::*};
+/// hashid_type!(
+///     /// Identifies a user
+///     pub UserId, HashIdB64, salt = b"users", min_len = 8
+/// );
+/// # fn main() {
+/// let id = UserId(42);
+/// let text = id.to_string();
+/// assert_eq!(8, text.len());
+/// assert_eq!(id, text.parse().unwrap());
+/// assert!("not an id".parse::<UserId>().is_err());
+/// # }
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! hashid_type {
+	(
+		$(#[$meta:meta])*
+		$vis:vis $name:ident, $variant:ty, salt = $salt:expr $(, min_len = $len:expr)? $(,)?
+	) => {
+		$(#[$meta])*
+		#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+		$vis struct $name(pub u64);
+
+		impl $name {
+			/// The encoder behind this type
+			$vis fn encoder() -> &'static $crate::hash::BoundedHashId<$variant> {
+				static ENCODER: ::std::sync::OnceLock<$crate::hash::BoundedHashId<$variant>> =
+					::std::sync::OnceLock::new();
+				ENCODER.get_or_init(|| {
+					let min_len: usize = 0 $(+ $len)?;
+					<$variant as $crate::variants::HashId>::with_salt_slice_and_len($salt, min_len)
+						.expect("salt longer than hash::MAX_SALT")
+				})
+			}
+		}
+
+		impl ::core::fmt::Display for $name {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+				match Self::encoder().encode_buf([self.0]) {
+					| ::core::option::Option::Some(id) => f.write_str(&id),
+					| ::core::option::Option::None => ::core::result::Result::Ok(()),
+				}
+			}
+		}
+
+		impl ::core::str::FromStr for $name {
+			type Err = $crate::prelude::DecodeErr<1>;
+			fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+				Self::encoder().decode(s).map(|[v]| Self(v))
+			}
+		}
+
+		impl ::core::convert::TryFrom<&str> for $name {
+			type Error = $crate::prelude::DecodeErr<1>;
+			fn try_from(s: &str) -> ::core::result::Result<Self, Self::Error> {
+				s.parse()
+			}
+		}
+
+		impl ::core::convert::From<u64> for $name {
+			fn from(v: u64) -> Self {
+				Self(v)
+			}
+		}
+
+		impl ::core::convert::From<$name> for u64 {
+			fn from(id: $name) -> u64 {
+				id.0
+			}
+		}
+
+		$crate::__hashid_type_serde!($name);
+	};
+}
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __hashid_type_serde {
+	($name:ident) => {
+		impl $crate::serde::Source for $name {
+			fn encode(values: &[u64]) -> ::core::option::Option<$crate::encoded::EncodedId> {
+				Self::encoder().encode_buf(values)
+			}
+			fn decode(
+				input: &[u8],
+				out: &mut [u64],
+			) -> ::core::result::Result<(), $crate::prelude::DecodeErr<0>> {
+				Self::encoder().decode_into(input, out)
+			}
+		}
+
+		impl $crate::serde::__serde::Serialize for $name {
+			fn serialize<S: $crate::serde::__serde::Serializer>(
+				&self,
+				serializer: S,
+			) -> ::core::result::Result<S::Ok, S::Error> {
+				$crate::serde::As::<Self>::serialize(&self.0, serializer)
+			}
+		}
+
+		impl<'de> $crate::serde::__serde::Deserialize<'de> for $name {
+			fn deserialize<D: $crate::serde::__serde::Deserializer<'de>>(
+				deserializer: D,
+			) -> ::core::result::Result<Self, D::Error> {
+				$crate::serde::As::<Self>::deserialize(deserializer).map(Self)
+			}
+		}
+	};
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __hashid_type_serde {
+	($name:ident) => {};
+}
@@ -0,0 +1,183 @@
+//! Self-describing IDs with a type prefix, like `usr_…` or `ord_…`.
+//!
+//! ```rust
+//! # use hashid_stack::{prefixed::Prefixed, prelude::*};
+//! # fn main() {
+//! let users = Prefixed::new("usr", HashIdB64::with_salt_and_len(b"1 2 3 4", 8)).unwrap();
+//! let orders = Prefixed::new("ord", HashIdB64::with_salt_and_len(b"1 2 3 4", 8)).unwrap();
+//!
+//! let id = users.encode([42]);
+//! assert!(id.starts_with("usr_"));
+//! assert_eq!([42], users.decode(&id).unwrap());
+//! assert!(matches!(
+//!     orders.decode::<1>(&id),
+//!     Err(DecodeErr::WrongPrefix { expected: "ord", .. })
+//! ));
+//! # }
+//! ```
+use crate::{
+	any::AnyHashId,
+	codec::HashIdCodec,
+	encoded::EncodedId,
+	hash::{Buffer, HashId},
+	lottery::Lottery,
+	shuffle::Shuffle,
+	util,
+	variants::HashId as Hash,
+};
+
+#[cfg(all(feature = "smartstring", feature = "std"))]
+use smartstring::alias::String;
+
+/// An encoder whose IDs are written as `prefix`, a separator and the ID.
+///
+/// Decoding checks and strips the prefix before handing the rest to the
+/// inner encoder. The prefix is public and only names the type; to also keep
+/// the IDs apart, see `with_context`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Prefixed<C> {
+	prefix: &'static str,
+	separator: u8,
+	ids: C,
+}
+
+impl<C: HashIdCodec> Prefixed<C> {
+	/// Wraps `ids` with `prefix` and a `_` separator.
+	///
+	/// Returns `None` unless the prefix is non-empty printable ASCII without
+	/// the separator in it.
+	pub fn new(prefix: &'static str, ids: C) -> Option<Self> {
+		Self {
+			prefix,
+			separator: b'_',
+			ids,
+		}
+		.checked()
+	}
+	/// Separates the prefix from the ID with `separator`, under the same
+	/// rules as [`Prefixed::new`].
+	pub fn with_separator(mut self, separator: u8) -> Option<Self> {
+		self.separator = separator;
+		self.checked()
+	}
+	fn checked(self) -> Option<Self> {
+		let sep = self.separator;
+		(sep.is_ascii_graphic()
+			&& !self.prefix.is_empty()
+			&& self.prefix.bytes().all(|b| b.is_ascii_graphic() && b != sep))
+		.then_some(self)
+	}
+	pub fn prefix(&self) -> &'static str {
+		self.prefix
+	}
+	pub fn separator(&self) -> u8 {
+		self.separator
+	}
+	/// The wrapped encoder
+	pub fn inner(&self) -> &C {
+		&self.ids
+	}
+
+	/// Encode an ID list behind the prefix
+	#[cfg(feature = "std")]
+	pub fn encode(&self, values: impl AsRef<[u64]>) -> String {
+		let mut s = String::new();
+		if let Some(id) = self.encode_buf(values) {
+			s.push_str(&id);
+		}
+		s
+	}
+	/// Encode an ID list behind the prefix. `None` if the inner encoder
+	/// gives nothing or the result doesn't fit in a [`Buffer`].
+	pub fn encode_buf(&self, values: impl AsRef<[u64]>) -> Option<EncodedId> {
+		let id = self.ids.encode_id(values.as_ref())?.into_inner();
+		if self.prefix.len() + 1 + id.len() > Buffer::capacity() {
+			return None;
+		}
+		let mut buf = Buffer::new();
+		buf.extend(self.prefix.as_bytes());
+		buf.push(self.separator);
+		buf.extend(id.as_slice());
+		Some(EncodedId::new(buf))
+	}
+
+	/// Checks and strips the prefix, then strictly decodes the rest.
+	pub fn decode<const OUT: usize>(
+		&self,
+		input: impl AsRef<[u8]>,
+	) -> Result<[u64; OUT], util::DecodeErr<OUT>> {
+		let mut out = [0; OUT];
+		match self.decode_into(input, &mut out) {
+			| Ok(()) => Ok(out),
+			| Err(err) => Err(err.with_values(out)),
+		}
+	}
+	/// Same as [`Prefixed::decode`], decoding exactly `out.len()` values into
+	/// `out`
+	pub fn decode_into(
+		&self,
+		input: impl AsRef<[u8]>,
+		out: &mut [u64],
+	) -> Result<(), util::DecodeErr<0>> {
+		let input = input.as_ref();
+		let rest = input
+			.strip_prefix(self.prefix.as_bytes())
+			.and_then(|rest| rest.strip_prefix(&[self.separator]));
+		match rest {
+			| Some(rest) => self.ids.decode_into(rest, out),
+			| None => Err(util::DecodeErr::WrongPrefix {
+				expected: self.prefix,
+				found: input
+					.iter()
+					.take_while(|&&b| b != self.separator)
+					.take(16)
+					.copied()
+					.collect(),
+			}),
+		}
+	}
+}
+
+impl<H: Hash, const SALT: usize, L: Lottery, S: Shuffle> Prefixed<HashId<H, SALT, L, S>>
+where
+	[(); H::SEP.len()]: Sized,
+	[(); H::REAL - H::GUARDS]: Sized,
+	[(); H::ALPH.len()]: Sized,
+	[(); H::REAL]: Sized,
+{
+	/// Sets the prefix as the inner encoder's
+	/// [context](HashId::with_context), so the same number encodes
+	/// differently under each prefix.
+	pub fn with_context(mut self) -> Self {
+		self.ids = self.ids.with_context(self.prefix.as_bytes());
+		self
+	}
+}
+
+impl Prefixed<AnyHashId> {
+	/// Sets the prefix as the inner encoder's
+	/// [context](AnyHashId::with_context).
+	pub fn with_context(mut self) -> Self {
+		self.ids = self.ids.with_context(self.prefix.as_bytes());
+		self
+	}
+}
+
+impl<C: HashIdCodec> HashIdCodec for Prefixed<C> {
+	fn encode_id(&self, values: &[u64]) -> Option<EncodedId> {
+		self.encode_buf(values)
+	}
+	fn decode_into(&self, input: &[u8], out: &mut [u64]) -> Result<(), util::DecodeErr<0>> {
+		Prefixed::decode_into(self, input, out)
+	}
+	fn variant(&self) -> &'static str {
+		self.ids.variant()
+	}
+	fn alphabet(&self) -> &'static [u8] {
+		self.ids.alphabet()
+	}
+	/// Inner minimum plus the prefix and separator
+	fn min_len(&self) -> usize {
+		self.ids.min_len() + self.prefix.len() + 1
+	}
+}
@@ -0,0 +1,161 @@
+//! Named encoders, set up once and looked up by name.
+//!
+//! ```rust
+//! # use hashid_stack::{any::{AnyHashId, Variant}, prelude::*, registry::Registry};
+//! # fn main() {
+//! let mut registry = Registry::new();
+//! registry.register("user", HashIdB32::with_salt_slice_and_len(b"users", 8).unwrap()).unwrap();
+//! registry.register("order", AnyHashId::new(Variant::Qr, b"orders", None).unwrap()).unwrap();
+//!
+//! let id = registry.get("user").unwrap().encode([42]);
+//! assert_eq!(8, id.len());
+//! assert_eq!(Some(("user", [42])), registry.decode_any(&id));
+//! # }
+//! ```
+use crate::any::AnyHashId;
+#[cfg(feature = "config")]
+use crate::config::{ConfigError, HashIdConfig};
+use std::{collections::HashMap, fmt};
+
+/// Why an encoder couldn't be registered. Both hold the name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RegistryError {
+	/// Another encoder already has this name
+	Duplicate(String),
+	/// The config couldn't be built, see [`HashIdConfig::build`]
+	#[cfg(feature = "config")]
+	Config(String, ConfigError),
+}
+
+impl fmt::Display for RegistryError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			| Self::Duplicate(name) => write!(f, "encoder {:?} is already registered", name),
+			#[cfg(feature = "config")]
+			| Self::Config(name, err) => write!(f, "encoder {:?}: {}", name, err),
+		}
+	}
+}
+
+impl std::error::Error for RegistryError {}
+
+/// Encoders by name, kept in registration order.
+///
+/// With the `zeroize` feature every encoder is wiped on drop.
+#[derive(Clone, Debug, Default)]
+pub struct Registry {
+	encoders: Vec<(String, AnyHashId)>,
+	index: HashMap<String, usize>,
+}
+
+impl Registry {
+	pub fn new() -> Self {
+		Self::default()
+	}
+	/// Builds a registry from named configs, failing on the first bad one
+	///
+	/// ```rust
+	/// # use hashid_stack::{any::Variant, config::HashIdConfig, registry::Registry};
+	/// # fn main() {
+	/// let mut user = HashIdConfig::new(Variant::B32, "users");
+	/// user.min_len = Some(8);
+	/// let order = HashIdConfig::new(Variant::Qr, "orders");
+	/// let registry = Registry::from_configs([("user", &user), ("order", &order)]).unwrap();
+	/// assert_eq!(8, registry.get("user").unwrap().encode([42]).len());
+	/// # }
+	/// ```
+	#[cfg(feature = "config")]
+	pub fn from_configs<'a>(
+		configs: impl IntoIterator<Item = (&'a str, &'a HashIdConfig)>,
+	) -> Result<Self, RegistryError> {
+		let mut registry = Self::new();
+		for (name, config) in configs {
+			registry.register_config(name, config)?;
+		}
+		Ok(registry)
+	}
+	/// Adds an encoder under `name`, which must not be taken yet
+	pub fn register(
+		&mut self,
+		name: impl Into<String>,
+		ids: impl Into<AnyHashId>,
+	) -> Result<(), RegistryError> {
+		let name = name.into();
+		if self.index.contains_key(&name) {
+			return Err(RegistryError::Duplicate(name));
+		}
+		self.index.insert(name.clone(), self.encoders.len());
+		self.encoders.push((name, ids.into()));
+		Ok(())
+	}
+	/// Builds and adds the encoder described by `config` under `name`
+	#[cfg(feature = "config")]
+	pub fn register_config(
+		&mut self,
+		name: impl Into<String>,
+		config: &HashIdConfig,
+	) -> Result<(), RegistryError> {
+		let name = name.into();
+		match config.build() {
+			| Ok(ids) => self.register(name, ids),
+			| Err(err) => Err(RegistryError::Config(name, err)),
+		}
+	}
+	pub fn get(&self, name: &str) -> Option<&AnyHashId> {
+		self.index.get(name).map(|&i| &self.encoders[i].1)
+	}
+	/// Names in registration order
+	pub fn names(&self) -> impl Iterator<Item = &str> {
+		self.encoders.iter().map(|(name, _)| name.as_str())
+	}
+	pub fn len(&self) -> usize {
+		self.encoders.len()
+	}
+	pub fn is_empty(&self) -> bool {
+		self.encoders.is_empty()
+	}
+	/// Strictly decodes `input` with each encoder in registration order, and
+	/// gives the name of the encoder that accepts it with the values.
+	///
+	/// Plain IDs are short and dense, so an encoder often accepts another's
+	/// IDs as some other value, around 1 in 60 of them even with different
+	/// contexts. The first encoder with a MAC to accept the input wins.
+	/// Otherwise exactly one encoder must accept it, and `None` is returned
+	/// if several do. MACs, or [`Prefixed`](crate::prefixed::Prefixed),
+	/// keep encoders apart reliably.
+	pub fn decode_any<const OUT: usize>(
+		&self,
+		input: impl AsRef<[u8]>,
+	) -> Option<(&str, [u64; OUT])> {
+		let input = input.as_ref();
+		let mut found = None;
+		for (name, ids) in &self.encoders {
+			if let Ok(out) = ids.decode(input) {
+				if ids.mac_len() > 0 {
+					return Some((name.as_str(), out));
+				}
+				if found.is_some() {
+					return None;
+				}
+				found = Some((name.as_str(), out));
+			}
+		}
+		found
+	}
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Registry {
+	fn zeroize(&mut self) {
+		for (_, ids) in &mut self.encoders {
+			ids.zeroize();
+		}
+	}
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Registry {
+	fn drop(&mut self) {
+		zeroize::Zeroize::zeroize(self);
+	}
+}
@@ -0,0 +1,201 @@
+//! Serde adapters writing integer fields as hash IDs.
+//!
+//! Implement [`Source`] on a marker type to pick the encoder, then point
+//! `#[serde(with = ...)]` at [`As`] for that marker. Fields can be a `u64`,
+//! an array or tuple of `u64`s, or an `Option` of any of those.
+//!
+//! ```rust
+//! # use hashid_stack::{prelude::*, serde::{As, Source}};
+//! # use serde::{Deserialize, Serialize};
+//! # use std::sync::LazyLock;
+//! static USERS: LazyLock<HashID<B64, 4>> = LazyLock::new(|| B64::with_salt(b"user"));
+//!
+//! struct Users;
+//! impl Source for Users {
+//!     fn encode(values: &[u64]) -> Option<EncodedId> {
+//!         USERS.encode_buf(values)
+//!     }
+//!     fn decode(input: &[u8], out: &mut [u64]) -> Result<(), DecodeErr<0>> {
+//!         USERS.decode_into(input, out)
+//!     }
+//! }
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct User {
+//!     #[serde(with = "As::<Users>")]
+//!     id: u64,
+//!     #[serde(with = "As::<Users>")]
+//!     invited_by: Option<u64>,
+//! }
+//! # fn main() {
+//! let json = serde_json::to_string(&User { id: 7, invited_by: None }).unwrap();
+//! assert_eq!(json, format!(r#"{{"id":"{}","invited_by":null}}"#, USERS.encode([7])));
+//! let user: User = serde_json::from_str(&json).unwrap();
+//! assert_eq!(7, user.id);
+//! # }
+//! ```
+use crate::{encoded::EncodedId, util::DecodeErr};
+use ::serde::{
+	de::{self, Deserializer, Visitor},
+	ser::{self, Serializer},
+};
+use core::{fmt, marker::PhantomData};
+
+#[doc(hidden)]
+pub use ::serde as __serde;
+
+/// Supplies the encoder used by [`As`].
+///
+/// Typically forwards to a `static` [`HashId`](crate::hash::HashId) through
+/// [`encode_buf`](crate::hash::HashId::encode_buf) and
+/// [`decode_into`](crate::hash::HashId::decode_into).
+pub trait Source {
+	/// Encodes `values`, or gives `None` if there are none
+	fn encode(values: &[u64]) -> Option<EncodedId>;
+	/// Decodes exactly `out.len()` values from `input` into `out`
+	fn decode(input: &[u8], out: &mut [u64]) -> Result<(), DecodeErr<0>>;
+}
+
+/// Field types [`As`] can write as a hash ID.
+pub trait Field: Sized {
+	fn serialize_as<S: Source, Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error>;
+	fn deserialize_as<'de, S: Source, D: Deserializer<'de>>(
+		deserializer: D,
+	) -> Result<Self, D::Error>;
+}
+
+/// Values making up a single hash ID.
+pub trait Values: Sized {
+	fn encode<S: Source>(&self) -> Option<EncodedId>;
+	fn decode<S: Source>(input: &[u8]) -> Result<Self, DecodeErr<0>>;
+}
+
+/// Adapter for `#[serde(with = "hashid_stack::serde::As::<S>")]`, encoding
+/// through the [`Source`] `S`.
+pub struct As<S: Source>(PhantomData<S>);
+
+impl<S: Source> As<S> {
+	pub fn serialize<T: Field, Z: Serializer>(value: &T, serializer: Z) -> Result<Z::Ok, Z::Error> {
+		value.serialize_as::<S, Z>(serializer)
+	}
+	pub fn deserialize<'de, T: Field, D: Deserializer<'de>>(
+		deserializer: D,
+	) -> Result<T, D::Error> {
+		T::deserialize_as::<S, D>(deserializer)
+	}
+}
+
+impl<T: Values> Field for T {
+	fn serialize_as<S: Source, Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+		let encoded = self
+			.encode::<S>()
+			.ok_or_else(|| <Z::Error as ser::Error>::custom("no values to encode as a hash ID"))?;
+		serializer.serialize_str(&encoded)
+	}
+	fn deserialize_as<'de, S: Source, D: Deserializer<'de>>(
+		deserializer: D,
+	) -> Result<Self, D::Error> {
+		deserializer.deserialize_str(IdVisitor::<S, T>(PhantomData))
+	}
+}
+
+impl<T: Values> Field for Option<T> {
+	fn serialize_as<S: Source, Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+		match self {
+			| Some(value) => serializer.serialize_some(&SomeId::<S, T>(value, PhantomData)),
+			| None => serializer.serialize_none(),
+		}
+	}
+	fn deserialize_as<'de, S: Source, D: Deserializer<'de>>(
+		deserializer: D,
+	) -> Result<Self, D::Error> {
+		deserializer.deserialize_option(OptionVisitor::<S, T>(PhantomData))
+	}
+}
+
+/// The value inside a `Some`, serialized through `S`
+struct SomeId<'a, S, T>(&'a T, PhantomData<S>);
+impl<S: Source, T: Values> ::serde::Serialize for SomeId<'_, S, T> {
+	fn serialize<Z: Serializer>(&self, serializer: Z) -> Result<Z::Ok, Z::Error> {
+		self.0.serialize_as::<S, Z>(serializer)
+	}
+}
+
+/// Decodes a string through `S`
+struct IdVisitor<S, T>(PhantomData<(S, T)>);
+impl<S: Source, T: Values> Visitor<'_> for IdVisitor<S, T> {
+	type Value = T;
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("a hash ID string")
+	}
+	fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+		self.visit_bytes(v.as_bytes())
+	}
+	fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<T, E> {
+		T::decode::<S>(v).map_err(|e| E::custom(format_args!("invalid hash ID: {}", e)))
+	}
+}
+
+/// Decodes an optional string through `S`
+struct OptionVisitor<S, T>(PhantomData<(S, T)>);
+impl<'de, S: Source, T: Values> Visitor<'de> for OptionVisitor<S, T> {
+	type Value = Option<T>;
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("a hash ID string or nothing")
+	}
+	fn visit_none<E: de::Error>(self) -> Result<Option<T>, E> {
+		Ok(None)
+	}
+	fn visit_unit<E: de::Error>(self) -> Result<Option<T>, E> {
+		Ok(None)
+	}
+	fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Option<T>, D::Error> {
+		T::deserialize_as::<S, D>(deserializer).map(Some)
+	}
+}
+
+impl Values for u64 {
+	fn encode<S: Source>(&self) -> Option<EncodedId> {
+		S::encode(core::slice::from_ref(self))
+	}
+	fn decode<S: Source>(input: &[u8]) -> Result<Self, DecodeErr<0>> {
+		let mut out = [0];
+		S::decode(input, &mut out).map(|_| out[0])
+	}
+}
+
+impl<const N: usize> Values for [u64; N] {
+	fn encode<S: Source>(&self) -> Option<EncodedId> {
+		S::encode(self)
+	}
+	fn decode<S: Source>(input: &[u8]) -> Result<Self, DecodeErr<0>> {
+		let mut out = [0; N];
+		S::decode(input, &mut out).map(|_| out)
+	}
+}
+
+/// Expands to `$with`, once for each `$v` it's repeated over
+macro_rules! each {
+	($v:ident, $with:tt) => {
+		$with
+	};
+}
+macro_rules! tuple {
+	($($v:ident),+) => {
+		impl Values for ($(each!($v, u64),)+) {
+			fn encode<S: Source>(&self) -> Option<EncodedId> {
+				let ($($v,)+) = *self;
+				S::encode(&[$($v),+])
+			}
+			fn decode<S: Source>(input: &[u8]) -> Result<Self, DecodeErr<0>> {
+				let mut out = [$(each!($v, 0)),+];
+				S::decode(input, &mut out)?;
+				let [$($v),+] = out;
+				Ok(($($v,)+))
+			}
+		}
+	};
+}
+tuple!(a, b);
+tuple!(a, b, c);
+tuple!(a, b, c, d);
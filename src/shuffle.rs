@@ -0,0 +1,59 @@
+//! Strategies for shuffling the alphabet.
+//!
+//! Every property of an ID comes down to the shuffle: it orders the alphabet
+//! and separators from the salt when a `HashId` is built, and reorders the
+//! alphabet from the lottery, salt and previous alphabet for every value.
+use crate::{sip::Sip, util};
+
+/// Shuffles `values` in place, driven by `key`.
+///
+/// Implementations must be deterministic, and must be permutations: every
+/// byte in `values` stays in it exactly once.
+pub trait Shuffle: Copy {
+	/// Stable name, hashed into [`HashId::fingerprint`](crate::hash::HashId::fingerprint)
+	const NAME: &'static str;
+	fn shuffle(values: &mut [u8], key: &[u8]);
+}
+
+/// The hashids "consistent shuffle".
+///
+/// This is the default and matches the output of other hashids
+/// implementations. An empty key leaves `values` untouched.
+#[derive(Clone, Copy, Debug)]
+pub struct Consistent;
+impl Shuffle for Consistent {
+	const NAME: &'static str = "consistent";
+	fn shuffle(values: &mut [u8], key: &[u8]) {
+		util::shuffle(values, key)
+	}
+}
+
+/// Fisher–Yates shuffle, with swaps drawn from SipHash-2-4 keyed by `key`.
+///
+/// Mixes far better than [`Consistent`], where small changes in the key
+/// often only move a few characters. Output is not compatible with other
+/// hashids implementations.
+///
+/// ```rust
+/// # use hashid_stack::{shuffle::Keyed, prelude::*};
+/// # fn main() {
+/// let ids = HashIdB64::with_salt(b"1 2 3 4").with_shuffle::<Keyed>();
+/// assert_eq!([5, 6], ids.decode(ids.encode([5, 6])).unwrap());
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Keyed;
+impl Shuffle for Keyed {
+	const NAME: &'static str = "keyed";
+	fn shuffle(values: &mut [u8], key: &[u8]) {
+		let half = |label: &[u8]| Sip::new(0, 0).write(label).write(key).finish();
+		let sip = Sip::new(
+			half(b"hashid-stack shuffle k0"),
+			half(b"hashid-stack shuffle k1"),
+		);
+		for i in (1..values.len()).rev() {
+			let j = sip.clone().write_u64(i as u64).finish() % (i as u64 + 1);
+			values.swap(i, j as usize);
+		}
+	}
+}
@@ -0,0 +1,157 @@
+//! Moving stored IDs from one encoder configuration to another.
+use crate::{
+	encoded::EncodedId,
+	hash::HashId,
+	lottery::Lottery,
+	shuffle::Shuffle,
+	util,
+	variants::HashId as Hash,
+};
+
+/// Re-encodes an ID from one configuration into another.
+///
+/// The input is decoded with the strict [`HashId::decode`], so only IDs that
+/// `from` would have produced itself are converted. The decoded values never
+/// leave this function. The number of values per ID is taken from the error
+/// type, `DecodeErr<OUT>`. Values that `to` can't fit in an ID, because of
+/// its `max_len` or the buffer capacity, fail with
+/// [`DecodeErr::Encode`](util::DecodeErr::Encode).
+///
+/// ```rust
+/// # use hashid_stack::prelude::*;
+/// # use hashid_stack::transcode::transcode;
+/// # fn main() {
+/// let old = HashIdDefault::with_salt(b"old");
+/// let new = HashIdB32::with_salt(b"new");
+/// let moved = transcode::<_, _, 3, 3, 2>(&old, &new, old.encode([1, 2])).unwrap();
+/// assert_eq!(new.encode([1, 2]), moved.as_str());
+/// # }
+/// ```
+pub fn transcode<A: Hash, B: Hash, const S1: usize, const S2: usize, const OUT: usize>(
+	from: &HashId<A, S1>,
+	to: &HashId<B, S2>,
+	input: impl AsRef<[u8]>,
+) -> Result<EncodedId, util::DecodeErr<OUT>>
+where
+	[(); A::SEP.len()]: Sized,
+	[(); A::REAL - A::GUARDS]: Sized,
+	[(); A::ALPH.len()]: Sized,
+	[(); A::REAL]: Sized,
+	[(); B::SEP.len()]: Sized,
+	[(); B::REAL - B::GUARDS]: Sized,
+	[(); B::ALPH.len()]: Sized,
+	[(); B::REAL]: Sized,
+{
+	transcode_with(from, to, input)
+}
+
+/// Streaming form of [`transcode`].
+///
+/// Yields one result per input, in order, so entries that fail to convert can
+/// be reported alongside their position.
+///
+/// ```rust
+/// # use hashid_stack::prelude::*;
+/// # use hashid_stack::transcode::transcode_all;
+/// # fn main() {
+/// let old = HashIdDefault::with_salt(b"old");
+/// let new = HashIdDefault::with_salt(b"new");
+/// let stored = [old.encode([1]), "not an id".into(), old.encode([3])];
+/// let failed: Vec<usize> = transcode_all::<_, _, 3, 3, 1, _>(&old, &new, &stored)
+///     .enumerate()
+///     .filter_map(|(i, res)| res.err().map(|_| i))
+///     .collect();
+/// assert_eq!(vec![1], failed);
+/// # }
+/// ```
+pub fn transcode_all<
+	'a,
+	A: Hash,
+	B: Hash,
+	const S1: usize,
+	const S2: usize,
+	const OUT: usize,
+	I: IntoIterator,
+>(
+	from: &'a HashId<A, S1>,
+	to: &'a HashId<B, S2>,
+	inputs: I,
+) -> impl Iterator<Item = Result<EncodedId, util::DecodeErr<OUT>>> + 'a
+where
+	I::Item: AsRef<[u8]>,
+	I::IntoIter: 'a,
+	[(); A::SEP.len()]: Sized,
+	[(); A::REAL - A::GUARDS]: Sized,
+	[(); A::ALPH.len()]: Sized,
+	[(); A::REAL]: Sized,
+	[(); B::SEP.len()]: Sized,
+	[(); B::REAL - B::GUARDS]: Sized,
+	[(); B::ALPH.len()]: Sized,
+	[(); B::REAL]: Sized,
+{
+	transcode_all_with(from, to, inputs)
+}
+
+/// Same as [`transcode`], for encoders with any [`Lottery`] and [`Shuffle`].
+pub fn transcode_with<
+	A: Hash,
+	B: Hash,
+	const S1: usize,
+	const S2: usize,
+	const OUT: usize,
+	L1: Lottery,
+	L2: Lottery,
+	Sh1: Shuffle,
+	Sh2: Shuffle,
+>(
+	from: &HashId<A, S1, L1, Sh1>,
+	to: &HashId<B, S2, L2, Sh2>,
+	input: impl AsRef<[u8]>,
+) -> Result<EncodedId, util::DecodeErr<OUT>>
+where
+	[(); A::SEP.len()]: Sized,
+	[(); A::REAL - A::GUARDS]: Sized,
+	[(); A::ALPH.len()]: Sized,
+	[(); A::REAL]: Sized,
+	[(); B::SEP.len()]: Sized,
+	[(); B::REAL - B::GUARDS]: Sized,
+	[(); B::ALPH.len()]: Sized,
+	[(); B::REAL]: Sized,
+{
+	let values = from.decode::<OUT>(input)?;
+	to.encode_buf(values).ok_or(util::DecodeErr::Encode)
+}
+
+/// Same as [`transcode_all`], for encoders with any [`Lottery`] and
+/// [`Shuffle`].
+pub fn transcode_all_with<
+	'a,
+	A: Hash,
+	B: Hash,
+	const S1: usize,
+	const S2: usize,
+	const OUT: usize,
+	I: IntoIterator,
+	L1: Lottery,
+	L2: Lottery,
+	Sh1: Shuffle,
+	Sh2: Shuffle,
+>(
+	from: &'a HashId<A, S1, L1, Sh1>,
+	to: &'a HashId<B, S2, L2, Sh2>,
+	inputs: I,
+) -> impl Iterator<Item = Result<EncodedId, util::DecodeErr<OUT>>> + 'a
+where
+	I::Item: AsRef<[u8]>,
+	I::IntoIter: 'a,
+	[(); A::SEP.len()]: Sized,
+	[(); A::REAL - A::GUARDS]: Sized,
+	[(); A::ALPH.len()]: Sized,
+	[(); A::REAL]: Sized,
+	[(); B::SEP.len()]: Sized,
+	[(); B::REAL - B::GUARDS]: Sized,
+	[(); B::ALPH.len()]: Sized,
+	[(); B::REAL]: Sized,
+{
+	inputs.into_iter().map(move |input| transcode_with(from, to, input))
+}
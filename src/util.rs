@@ -1,3 +1,6 @@
+use crate::bytevec::ByteVec;
+use core::fmt;
+
 /// Shuffles the ID strings
 pub(crate) fn shuffle(values: &mut [u8], salt: &[u8]) {
 	if salt.is_empty() {
@@ -9,7 +12,7 @@ pub(crate) fn shuffle(values: &mut [u8], salt: &[u8]) {
 	// Setup loop-pre
 	let (mut v, mut p) = (Wrapping(0), Wrapping(0));
 	// Change from doing weird shit to doing... well, even weirder shit.
-	for i in (1..values.len()).rev().map(|i| Wrapping(i)) {
+	for i in (1..values.len()).rev().map(Wrapping) {
 		v %= salt.len();
 		let n = Wrapping(*unsafe {
 			// SAFETY: we know exactly how long the salt is anyway, and we're modulating the index by it.
@@ -28,7 +31,7 @@ pub(crate) fn shuffle(values: &mut [u8], salt: &[u8]) {
 }
 
 /// Creates a numerically weighted hash
-pub(crate) fn make_nhash(values: &[u64]) -> u64 {
+pub(crate) fn make_nhash(values: impl IntoIterator<Item = u64>) -> u64 {
 	values
 		.into_iter()
 		.enumerate()
@@ -71,12 +74,12 @@ pub(crate) fn unhash<const A: usize>(input: &[u8], alph: [u8; A]) -> Option<u64>
 	//	let lookup: [Option<U8>; 256] = transmute(lookup);
 	//	lookup
 	//};
-	input.into_iter().enumerate().fold(Some(0), |a, (i, &v)| {
+	input.iter().enumerate().try_fold(0, |a: u64, (i, &v)| {
 		//let pos = unsafe { *lookup.get_unchecked(v as usize) }?.get() as usize;
 		let pos = alph.into_iter().position(|it| it == v)?;
 		let b = A.checked_pow((input.len() - i - 1).try_into().ok()?)?;
 		let c = pos.checked_mul(b)?;
-		a.map(|a| a + c as u64)
+		a.checked_add(c as u64)
 	})
 }
 #[derive(Clone, Copy, Debug)]
@@ -84,8 +87,115 @@ pub enum DecodeErr<const N: usize> {
 	Value(usize, [u64; N]),
 	Hash,
 	Items(usize, usize),
+	/// The tag appended by `HashId::with_mac` is missing or doesn't match
+	Mac,
+	/// The check character from `HashId::with_check_char` doesn't match,
+	/// most likely a typo
+	Check,
+	/// An ID from `HashId::encode_expiring` outlived its TTL. Holds the
+	/// time it was issued at.
+	Expired(u64),
+	/// An ID from `HashId::encode_expiring` was issued further in the
+	/// future than the allowed clock skew. Holds the time it claims.
+	Future(u64),
+	/// The input decoded, but the target of `transcode::transcode` can't
+	/// encode the values, as the ID would be over its `max_len` or the
+	/// buffer capacity
+	Encode,
+	/// More than one configuration of a `keyring::KeyRing` accepted the
+	/// input. Holds the indices of the first two.
+	Ambiguous(usize, usize),
+	/// The version character read by `versioned::Versioned` isn't
+	/// registered. Holds the character.
+	Version(u8),
+	/// The input doesn't start with the prefix of `prefixed::Prefixed`.
+	/// `found` holds what came before the separator, cut to 16 bytes.
+	WrongPrefix {
+		expected: &'static str,
+		found: ByteVec<16>,
+	},
+}
+
+impl<const N: usize> DecodeErr<N> {
+	/// Converts an error from decoding `N` values into one for `M`, for
+	/// callers that decode extra values of their own. Counts are shifted by
+	/// the difference and partial values are truncated or zero-filled.
+	pub(crate) fn resize<const M: usize>(self) -> DecodeErr<M> {
+		let shift = |n: usize| (n + M).saturating_sub(N);
+		match self {
+			| Self::Value(idx, values) => {
+				let mut out = [0; M];
+				let len = M.min(N);
+				out[..len].copy_from_slice(&values[..len]);
+				DecodeErr::Value(idx.min(M), out)
+			}
+			| Self::Hash => DecodeErr::Hash,
+			| Self::Items(expected, found) => DecodeErr::Items(shift(expected), shift(found)),
+			| Self::Mac => DecodeErr::Mac,
+			| Self::Check => DecodeErr::Check,
+			| Self::Expired(issued_at) => DecodeErr::Expired(issued_at),
+			| Self::Future(issued_at) => DecodeErr::Future(issued_at),
+			| Self::Encode => DecodeErr::Encode,
+			| Self::Ambiguous(a, b) => DecodeErr::Ambiguous(a, b),
+			| Self::Version(symbol) => DecodeErr::Version(symbol),
+			| Self::WrongPrefix { expected, found } => DecodeErr::WrongPrefix { expected, found },
+		}
+	}
+}
+
+impl DecodeErr<0> {
+	/// Attaches the values decoded so far to an error from decoding into a
+	/// slice.
+	pub(crate) fn with_values<const M: usize>(self, values: [u64; M]) -> DecodeErr<M> {
+		match self {
+			| Self::Value(idx, []) => DecodeErr::Value(idx, values),
+			| Self::Hash => DecodeErr::Hash,
+			| Self::Items(expected, found) => DecodeErr::Items(expected, found),
+			| Self::Mac => DecodeErr::Mac,
+			| Self::Check => DecodeErr::Check,
+			| Self::Expired(issued_at) => DecodeErr::Expired(issued_at),
+			| Self::Future(issued_at) => DecodeErr::Future(issued_at),
+			| Self::Encode => DecodeErr::Encode,
+			| Self::Ambiguous(a, b) => DecodeErr::Ambiguous(a, b),
+			| Self::Version(symbol) => DecodeErr::Version(symbol),
+			| Self::WrongPrefix { expected, found } => DecodeErr::WrongPrefix { expected, found },
+		}
+	}
+}
+
+impl<const N: usize> fmt::Display for DecodeErr<N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			| Self::Value(idx, _) => write!(f, "value {} is not valid for this alphabet", idx),
+			| Self::Hash => f.write_str("not an ID this encoder produces"),
+			| Self::Items(expected, found) => write!(f, "expected {} values, found {}", expected, found),
+			| Self::Mac => f.write_str("MAC tag is missing or doesn't match"),
+			| Self::Check => f.write_str("check character doesn't match"),
+			| Self::Expired(issued_at) => write!(f, "expired, issued at {}", issued_at),
+			| Self::Future(issued_at) => write!(f, "issued in the future, at {}", issued_at),
+			| Self::Encode => f.write_str("decoded, but too long for the target encoder"),
+			| Self::Ambiguous(a, b) => write!(f, "accepted by both key {} and key {}", a, b),
+			| Self::Version(symbol) => {
+				write!(f, "unknown version {:?}", char::from(*symbol))
+			}
+			| Self::WrongPrefix { expected, found } => write!(
+				f,
+				"expected prefix {:?}, found \"{}\"",
+				expected,
+				found.as_slice().escape_ascii()
+			),
+		}
+	}
+}
+
+impl<const N: usize> core::error::Error for DecodeErr<N> {}
+
+/// Compares two byte strings without exiting early on the first difference.
+pub(crate) fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+	a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
 }
 
-pub(crate) const fn garbage<T>() -> T {
-	unsafe { core::mem::MaybeUninit::uninit().assume_init() }
+/// Scratch byte buffer. Zero-filled, so unused tails compare equal.
+pub(crate) const fn garbage<const N: usize>() -> [u8; N] {
+	[0; N]
 }
@@ -11,6 +11,8 @@ const GUARD_DIV: usize = 12;
 
 /// Main implementation for hash IDs.
 pub trait HashId: Sized + Sealed {
+	/// Short name: `default`, `qr`, `b64` or `b32`
+	const NAME: &'static str;
 	/// Alphabet
 	const ALPH: &'static [u8];
 	/// Separators
@@ -23,8 +25,7 @@ pub trait HashId: Sized + Sealed {
 	/// SAFETY: Implemented internally only. Produces a sanitised alphabet.
 	fn filtered() -> [u8; Self::REAL] {
 		use crate::bytevec::ByteVec;
-		let b: ByteVec<{ Self::REAL }>;
-		b = Self::ALPH
+		let b: ByteVec<{ Self::REAL }> = Self::ALPH
 			.iter()
 			.filter(|c| !Self::SEP.contains(c))
 			.copied()
@@ -54,12 +55,67 @@ pub trait HashId: Sized + Sealed {
 	{
 		hash::HashId::init_salt_len(salt, min_len.into())
 	}
+	/// Generates a hashid instance from a passphrase, via [`kdf::derive_salt`](crate::kdf::derive_salt)
+	#[cfg(feature = "kdf")]
+	fn with_passphrase(
+		passphrase: impl AsRef<[u8]>,
+	) -> crate::kdf::PassphraseHashId<Self>
+	where
+		[(); Self::SEP.len()]: Sized,
+		[(); Self::ALPH.len()]: Sized,
+		[(); Self::REAL]: Sized,
+		[(); Self::REAL - Self::GUARDS]: Sized,
+	{
+		hash::HashId::init_salt_len(&crate::kdf::derive_salt(passphrase), None)
+	}
+	/// Generates a hashid instance from a passphrase and length
+	#[cfg(feature = "kdf")]
+	fn with_passphrase_and_len(
+		passphrase: impl AsRef<[u8]>,
+		min_len: impl Into<Option<usize>>,
+	) -> crate::kdf::PassphraseHashId<Self>
+	where
+		[(); Self::SEP.len()]: Sized,
+		[(); Self::ALPH.len()]: Sized,
+		[(); Self::REAL]: Sized,
+		[(); Self::REAL - Self::GUARDS]: Sized,
+	{
+		hash::HashId::init_salt_len(&crate::kdf::derive_salt(passphrase), min_len.into())
+	}
+	/// Generates a hashid instance using a salt only known at runtime.
+	///
+	/// Returns `None` if the salt is longer than [`hash::MAX_SALT`].
+	fn with_salt_slice(salt: &[u8]) -> Option<hash::BoundedHashId<Self>>
+	where
+		[(); Self::SEP.len()]: Sized,
+		[(); Self::ALPH.len()]: Sized,
+		[(); Self::REAL]: Sized,
+		[(); Self::REAL - Self::GUARDS]: Sized,
+	{
+		hash::HashId::init_salt_slice(salt, None)
+	}
+	/// Generates a hashid instance using a runtime salt and length
+	///
+	/// Returns `None` if the salt is longer than [`hash::MAX_SALT`].
+	fn with_salt_slice_and_len(
+		salt: &[u8],
+		min_len: impl Into<Option<usize>>,
+	) -> Option<hash::BoundedHashId<Self>>
+	where
+		[(); Self::SEP.len()]: Sized,
+		[(); Self::ALPH.len()]: Sized,
+		[(); Self::REAL]: Sized,
+		[(); Self::REAL - Self::GUARDS]: Sized,
+	{
+		hash::HashId::init_salt_slice(salt, min_len.into())
+	}
 }
 /// Generic HashID implementation, using full alphabet
 #[derive(Debug, Clone, Copy)]
 pub struct HashIdDefault;
 impl Sealed for HashIdDefault {}
 impl HashId for HashIdDefault {
+	const NAME: &'static str = "default";
 	const ALPH: &'static [u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890";
 	const SEP: &'static [u8] = b"cfhistuCFHISTU";
 }
@@ -71,6 +127,7 @@ impl HashId for HashIdDefault {
 pub struct HashIdQr;
 impl Sealed for HashIdQr {}
 impl HashId for HashIdQr {
+	const NAME: &'static str = "qr";
 	// Technically this isn't URL-safe, however
 	const ALPH: &'static [u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
 	const SEP: &'static [u8] = b"CFHISTU";
@@ -81,6 +138,7 @@ impl HashId for HashIdQr {
 pub struct HashIdB64;
 impl Sealed for HashIdB64 {}
 impl HashId for HashIdB64 {
+	const NAME: &'static str = "b64";
 	const ALPH: &'static [u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz1234567890-_";
 	// well wtf this breaks if I don't have at least - or _ in it lol
 	const SEP: &'static [u8] = b"CFHISTUcfhistu-_";
@@ -90,6 +148,7 @@ impl HashId for HashIdB64 {
 pub struct HashIdB32;
 impl Sealed for HashIdB32 {}
 impl HashId for HashIdB32 {
+	const NAME: &'static str = "b32";
 	const ALPH: &'static [u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
 	const SEP: &'static [u8] = b"CFHISTU";
 }
@@ -0,0 +1,123 @@
+//! IDs tagged with the version of the encoder that wrote them.
+use crate::{
+	any::AnyHashId,
+	encoded::EncodedId,
+	hash::Buffer,
+	util,
+};
+
+#[cfg(all(feature = "smartstring", feature = "std"))]
+use smartstring::alias::String;
+
+/// Encoders tagged with a version character, for changing salts or
+/// alphabets over time without a lookup table.
+///
+/// Every ID carries the character of the version that encoded it, leading
+/// by default or trailing after [`Versioned::trailing`]. New IDs always use
+/// the current version. Decoding reads the character and hands the rest to
+/// that version, so older IDs keep working as long as their version is
+/// registered.
+///
+/// ```rust
+/// # use hashid_stack::{any::{AnyHashId, Variant}, versioned::Versioned};
+/// # fn main() {
+/// let v1 = AnyHashId::new(Variant::B64, b"old salt", None).unwrap();
+/// let v2 = AnyHashId::new(Variant::B32, b"new salt", 8).unwrap();
+/// let old = Versioned::new([(b'1', v1)], b'1').unwrap();
+/// let ids = Versioned::new([(b'1', v1), (b'2', v2)], b'2').unwrap();
+///
+/// let id = ids.encode([42]);
+/// assert!(id.starts_with('2'));
+/// assert_eq!(([42], b'2'), ids.decode(&id).unwrap());
+/// assert_eq!(([42], b'1'), ids.decode(old.encode([42])).unwrap());
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Versioned<const N: usize> {
+	versions: [(u8, AnyHashId); N],
+	/// Index of the version used for encoding
+	current: usize,
+	trailing: bool,
+}
+
+impl<const N: usize> Versioned<N> {
+	/// Registers each encoder under its version character and encodes with
+	/// `current`.
+	///
+	/// Returns `None` if a character isn't printable ASCII or is used twice,
+	/// or if `current` isn't one of them.
+	pub fn new(versions: [(u8, AnyHashId); N], current: u8) -> Option<Self> {
+		for (i, &(symbol, _)) in versions.iter().enumerate() {
+			if !symbol.is_ascii_graphic() || versions[..i].iter().any(|&(s, _)| s == symbol) {
+				return None;
+			}
+		}
+		Some(Self {
+			versions,
+			current: versions.iter().position(|&(s, _)| s == current)?,
+			trailing: false,
+		})
+	}
+	/// Puts the version character at the end of IDs instead of the start
+	pub fn trailing(mut self) -> Self {
+		self.trailing = true;
+		self
+	}
+	/// The version character and encoder used for encoding
+	pub fn current(&self) -> (u8, &AnyHashId) {
+		let (symbol, ids) = &self.versions[self.current];
+		(*symbol, ids)
+	}
+	/// The encoder registered under `symbol`
+	pub fn get(&self, symbol: u8) -> Option<&AnyHashId> {
+		self.versions.iter().find(|(s, _)| *s == symbol).map(|(_, ids)| ids)
+	}
+
+	/// Encode an ID list with the current version
+	#[cfg(feature = "std")]
+	pub fn encode(&self, values: impl AsRef<[u64]>) -> String {
+		let mut s = String::new();
+		if let Some(id) = self.encode_buf(values) {
+			s.push_str(&id);
+		}
+		s
+	}
+	/// Encode an ID list with the current version. `None` if the encoder
+	/// gives nothing or the ID leaves no room for the version character.
+	pub fn encode_buf(&self, values: impl AsRef<[u64]>) -> Option<EncodedId> {
+		let (symbol, ids) = self.current();
+		let id = ids.encode_buf(values)?.into_inner();
+		if id.len() >= Buffer::capacity() {
+			return None;
+		}
+		let mut buf = Buffer::new();
+		if !self.trailing {
+			buf.push(symbol);
+		}
+		buf.extend(id.as_slice());
+		if self.trailing {
+			buf.push(symbol);
+		}
+		Some(EncodedId::new(buf))
+	}
+
+	/// Decodes a value with the version named in the input, returning it with
+	/// the version character.
+	///
+	/// Unregistered characters fail with
+	/// [`DecodeErr::Version`](util::DecodeErr::Version), and the rest is
+	/// decoded with the strict [`decode`](crate::hash::HashId::decode).
+	pub fn decode<const OUT: usize>(
+		&self,
+		input: impl AsRef<[u8]>,
+	) -> Result<([u64; OUT], u8), util::DecodeErr<OUT>> {
+		let input = input.as_ref();
+		let (symbol, rest) = match self.trailing {
+			| false => input.split_first(),
+			| true => input.split_last(),
+		}
+		.ok_or(util::DecodeErr::Hash)?;
+		let ids = self.get(*symbol).ok_or(util::DecodeErr::Version(*symbol))?;
+		Ok((ids.decode(rest)?, *symbol))
+	}
+}
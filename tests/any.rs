@@ -0,0 +1,51 @@
+use hashid_stack::{
+  any::{AnyHashId, UnknownVariant, Variant},
+  prelude::*,
+};
+
+#[test]
+fn parses_variant_names() {
+  for v in Variant::ALL {
+    assert_eq!(Ok(v), v.name().parse());
+    assert_eq!(Ok(v), v.to_string().to_uppercase().parse());
+  }
+  assert_eq!(Err(UnknownVariant), "base58".parse::<Variant>());
+  assert_eq!(Err(UnknownVariant), "".parse::<Variant>());
+}
+
+#[test]
+fn matches_the_static_encoders() {
+  let salt = b"runtime salt";
+  let cases = [
+    ("default", HashIdDefault::with_salt_and_len(salt, 6).encode([9, 10])),
+    ("qr", HashIdQr::with_salt_and_len(salt, 6).encode([9, 10])),
+    ("b64", HashIdB64::with_salt_and_len(salt, 6).encode([9, 10])),
+    ("b32", HashIdB32::with_salt_and_len(salt, 6).encode([9, 10])),
+  ];
+  for (name, expected) in cases {
+    let ids = AnyHashId::new(name.parse().unwrap(), salt, 6).unwrap();
+    assert_eq!(name, ids.variant().name());
+    assert_eq!(expected, ids.encode([9, 10]));
+    assert_eq!(expected, ids.encode_buf([9, 10]).unwrap().as_str());
+    assert_eq!([9, 10], ids.decode(&expected).unwrap());
+    assert_eq!([9, 10], ids.decode_fast(&expected).unwrap());
+    let mut out = [0; 2];
+    ids.decode_into(&expected, &mut out).unwrap();
+    assert_eq!([9, 10], out);
+    assert_eq!(6, ids.codec().min_len());
+  }
+}
+
+#[test]
+fn rejects_oversized_salts() {
+  assert!(AnyHashId::new(Variant::B32, &[0; 65], None).is_none());
+  assert!(AnyHashId::new(Variant::B32, &[0; 64], None).is_some());
+}
+
+#[test]
+fn wraps_configured_encoders() {
+  let ids = HashIdQr::with_salt_slice(b"qr").unwrap().with_check_char();
+  let any = AnyHashId::from(ids);
+  assert_eq!(Variant::Qr, any.variant());
+  assert_eq!(ids.encode([77]), any.encode([77]));
+}
@@ -20,3 +20,50 @@ fn should_fail_for_decoding_nothing() {
 fn should_fail_for_decoding_invalid_id() {
   let _: [u64; 1] = d().decode_fast("f").unwrap();
 }
+
+#[test]
+fn should_fail_for_decoding_values_past_u64_max() {
+  // Some of these decode to more than u64::MAX, which must fail rather
+  // than overflow.
+  let ids = HashIdQr::with_salt(b"1 2 3 4");
+  for len in 2..16 {
+    for &lead in HashIdQr::ALPH {
+      for &fill in HashIdQr::ALPH {
+        let mut input = vec![lead; 2];
+        input.resize(len, fill);
+        let _ = ids.decode_fast::<1>(&input);
+      }
+    }
+  }
+}
+
+#[test]
+fn should_fail_for_encoding_more_than_fits() {
+  let ids = HashIdB32::with_salt(b"1 2 3 4");
+  assert!(ids.encode_buf([u64::MAX; 20]).is_none());
+  assert_eq!("", ids.encode([u64::MAX; 20]));
+  assert!(ids.encode_buf([u64::MAX; 4]).is_some());
+}
+
+#[test]
+fn should_pad_past_one_alphabet_round() {
+  for len in [30, 40, 100] {
+    let ids = HashIdB32::with_salt_and_len(b"x", len);
+    for v in [0, 1, u64::MAX] {
+      let id = ids.encode([v]);
+      assert_eq!(len, id.len());
+      assert_eq!([v], ids.decode(&id).unwrap());
+    }
+  }
+}
+
+#[test]
+fn should_clamp_min_len_to_fit() {
+  let ids = HashIdB64::with_salt_and_len(b"x", 1000);
+  assert_eq!(hashid_stack::hash::MAX_MIN_LEN, ids.min_len());
+  let ids = ids.with_mac(*b"sixteen byte key", 8).with_check_char();
+  assert_eq!(ids.max_min_len(), ids.min_len());
+  let id = ids.encode([u64::MAX, 1]);
+  assert_eq!(hashid_stack::hash::MAX_MIN_LEN, id.len());
+  assert_eq!([u64::MAX, 1], ids.decode(&id).unwrap());
+}
@@ -0,0 +1,43 @@
+use hashid_stack::prelude::*;
+
+const SALTS: [&[u8]; 4] = [b"", b"   ", b"this is my salt", b"`~!@#$%^&*()-_=+\\|'\";:/?.>,<{[}]"];
+
+macro_rules! same_output {
+  ($($t:ty),*) => {$(
+    for salt in SALTS {
+      let bounded = <$t>::with_salt_slice_and_len(salt, 12).expect("salt fits");
+      for data in [&[1u64][..], &[1, 2, 3], &[1000, 2000, 3000, 4000], &[u64::MAX, 0, 7]] {
+        let enc = bounded.encode(data);
+        let fixed = match salt.len() {
+          0 => <$t>::with_salt_and_len(b"", 12).encode(data),
+          3 => <$t>::with_salt_and_len(b"   ", 12).encode(data),
+          15 => <$t>::with_salt_and_len(b"this is my salt", 12).encode(data),
+          _ => <$t>::with_salt_and_len(b"`~!@#$%^&*()-_=+\\|'\";:/?.>,<{[}]", 12).encode(data),
+        };
+        assert_eq!(fixed, enc, "{} output differs for salt {:?}", stringify!($t), salt);
+      }
+      let dec: [u64; 3] = bounded.decode(bounded.encode([4, 5, 6])).expect(stringify!($t));
+      assert_eq!([4, 5, 6], dec);
+    }
+  )*}
+}
+
+#[test]
+fn matches_fixed_salt() {
+  same_output!(HashIdDefault, HashIdQr, HashIdB32, HashIdB64);
+}
+
+#[test]
+fn rejects_oversized_salt() {
+  assert!(HashIdB64::with_salt_slice(&[b'x'; 65]).is_none());
+  assert!(HashIdB64::with_salt_slice(&[b'x'; 64]).is_some());
+}
+
+#[test]
+fn one_type_for_all_salts() {
+  let tenants: Vec<BoundedHashId<HashIdB32>> = ["tenant-a", "tenant-b", "a much longer tenant salt"]
+    .iter()
+    .map(|s| HashIdB32::with_salt_slice(s.as_bytes()).unwrap())
+    .collect();
+  assert_ne!(tenants[0].encode([1]), tenants[1].encode([1]));
+}
@@ -0,0 +1,38 @@
+use hashid_stack::prelude::*;
+
+macro_rules! every_typo_caught {
+  ($($t:ty),*) => {$(
+    let ids = <$t>::with_salt_and_len(b"support desk", 8).with_check_char();
+    let alph = <$t as HashId>::ALPH;
+    for data in [[1u64, 2], [31337, 0], [u64::MAX, 42]] {
+      let id = ids.encode(data);
+      assert_eq!(data, ids.decode::<2>(&id).expect(stringify!($t)));
+      let mut bytes = id.as_bytes().to_vec();
+      for i in 0..bytes.len() {
+        let orig = bytes[i];
+        for &c in alph.iter().filter(|&&c| c != orig) {
+          bytes[i] = c;
+          assert!(
+            matches!(ids.decode_fast::<2>(&bytes), Err(DecodeErr::Check)),
+            "{}: {:?} not caught", stringify!($t), String::from_utf8_lossy(&bytes)
+          );
+        }
+        bytes[i] = orig;
+      }
+    }
+  )*}
+}
+
+#[test]
+fn single_substitutions() {
+  every_typo_caught!(HashIdDefault, HashIdQr, HashIdB32, HashIdB64);
+}
+
+#[test]
+fn composes_with_mac() {
+  let ids = HashIdB64::with_salt(b"salt").with_mac(*b"0123456789abcdef", 4).with_check_char();
+  let id = ids.encode([5, 6, 7]);
+  assert_eq!([5, 6, 7], ids.decode::<3>(&id).unwrap());
+  assert!(matches!(ids.decode::<3>(""), Err(DecodeErr::Check)));
+  assert!(matches!(ids.decode::<3>("not valid!"), Err(DecodeErr::Check)));
+}
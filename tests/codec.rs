@@ -0,0 +1,49 @@
+use hashid_stack::{codec::HashIdCodec, lottery::Keyed, prelude::*, shuffle};
+
+#[test]
+fn mixed_encoders_share_a_collection() {
+  let b32 = HashIdB32::with_salt(b"a");
+  let qr = HashIdQr::with_salt_and_len(b"longer salt", 12);
+  let b64 = HashIdB64::with_salt_slice(b"bounded").unwrap().with_mac(*b"sixteen byte key", 3);
+  let default = HashIdDefault::with_salt(b"x").with_lottery::<Keyed>().with_shuffle::<shuffle::Keyed>();
+  let codecs: Vec<(Box<dyn HashIdCodec>, String)> = vec![
+    (Box::new(b32), b32.encode([5, 6]).to_string()),
+    (Box::new(qr), qr.encode([5, 6]).to_string()),
+    (Box::new(b64), b64.encode([5, 6]).to_string()),
+    (Box::new(default), default.encode([5, 6]).to_string()),
+  ];
+  for (codec, expected) in &codecs {
+    let mut id = String::new();
+    codec.encode_to(&[5, 6], &mut id).unwrap();
+    assert_eq!(*expected, id);
+    assert_eq!(Some(expected.as_str()), codec.encode_id(&[5, 6]).as_deref());
+
+    let mut out = [0; 2];
+    codec.decode_into(id.as_bytes(), &mut out).unwrap();
+    assert_eq!([5, 6], out);
+    let mut one = [0; 1];
+    assert!(matches!(codec.decode_into(id.as_bytes(), &mut one), Err(DecodeErr::Items(1, 2))));
+  }
+}
+
+#[test]
+fn metadata() {
+  let codecs: [&dyn HashIdCodec; 4] = [
+    &HashIdDefault::with_salt(b"s"),
+    &HashIdQr::with_salt(b"s"),
+    &HashIdB64::with_salt_and_len(b"s", 9),
+    &HashIdB32::with_salt(b"s"),
+  ];
+  let names: Vec<_> = codecs.iter().map(|c| c.variant()).collect();
+  assert_eq!(["default", "qr", "b64", "b32"], names[..]);
+  assert_eq!(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567", codecs[3].alphabet());
+  assert_eq!([0, 0, 9, 0], codecs.map(|c| c.min_len()));
+}
+
+#[test]
+fn empty_writes_nothing() {
+  let codec: &dyn HashIdCodec = &HashIdB32::with_salt(b"s");
+  let mut id = String::new();
+  codec.encode_to(&[], &mut id).unwrap();
+  assert!(id.is_empty());
+}
@@ -0,0 +1,153 @@
+#![cfg(feature = "config")]
+use hashid_stack::{
+  any::Variant,
+  config::{ConfigError, HashIdConfig, SaltEncoding, MAX_MIN_LEN},
+  hash,
+  prelude::*,
+};
+use std::collections::HashMap;
+
+fn json(s: &str) -> HashIdConfig {
+  serde_json::from_str(s).unwrap()
+}
+
+#[test]
+fn defaults() {
+  let config = json(r#"{ "salt": "salt" }"#);
+  assert_eq!(Variant::Default, config.variant);
+  assert_eq!(SaltEncoding::Utf8, config.salt_encoding);
+  let ids = config.build().unwrap();
+  assert_eq!(HashIdDefault::with_salt(b"salt").encode([7]), ids.encode([7]));
+}
+
+#[test]
+fn salt_encodings_agree() {
+  let utf8 = json(r#"{ "variant": "B64", "salt": "secret salt", "min_len": 10 }"#);
+  let hex = json(
+    r#"{ "variant": "b64", "salt": "7365637265742073616C74", "salt_encoding": "hex", "min_len": 10 }"#,
+  );
+  let base64 = json(
+    r#"{ "variant": "b64", "salt": "c2VjcmV0IHNhbHQ=", "salt_encoding": "base64", "min_len": 10 }"#,
+  );
+  let unpadded = json(
+    r#"{ "variant": "b64", "salt": "c2VjcmV0IHNhbHQ", "salt_encoding": "base64", "min_len": 10 }"#,
+  );
+  assert_eq!(b"secret salt".to_vec(), base64.salt_bytes().unwrap());
+  let expected = HashIdB64::with_salt_and_len(b"secret salt", 10).encode([1, 2]);
+  for config in [&utf8, &hex, &base64, &unpadded] {
+    assert_eq!(expected, config.build().unwrap().encode([1, 2]));
+  }
+}
+
+#[test]
+fn base64_alphabets() {
+  let mut config = HashIdConfig::new(Variant::Default, "-_8=");
+  config.salt_encoding = SaltEncoding::Base64;
+  assert_eq!(vec![0xfb, 0xff], config.salt_bytes().unwrap());
+  config.salt = "+/8=".into();
+  assert_eq!(vec![0xfb, 0xff], config.salt_bytes().unwrap());
+  config.salt = "abcde".into();
+  assert_eq!(Err(ConfigError::SaltEncoding(SaltEncoding::Base64)), config.salt_bytes());
+}
+
+#[test]
+fn rejects_bad_settings() {
+  assert!(serde_json::from_str::<HashIdConfig>(r#"{ "salt": "s", "variant": "b16" }"#).is_err());
+  assert!(serde_json::from_str::<HashIdConfig>(r#"{ "salt": "s", "min_lenn": 3 }"#).is_err());
+  assert!(serde_json::from_str::<HashIdConfig>(r#"{ "min_len": 3 }"#).is_err());
+
+  let mut config = HashIdConfig::new(Variant::Qr, "abc");
+  config.salt_encoding = SaltEncoding::Hex;
+  assert_eq!(Err(ConfigError::SaltEncoding(SaltEncoding::Hex)), config.validate());
+
+  let config = HashIdConfig::new(Variant::Qr, "x".repeat(hash::MAX_SALT + 1));
+  assert_eq!(Err(ConfigError::SaltTooLong(hash::MAX_SALT + 1)), config.validate());
+
+  let mut config = HashIdConfig::new(Variant::Qr, "salt");
+  config.min_len = Some(MAX_MIN_LEN + 1);
+  assert_eq!(Err(ConfigError::MinLen(MAX_MIN_LEN + 1)), config.validate());
+
+  config.min_len = Some(10);
+  config.max_len = Some(8);
+  assert_eq!(
+    Err(ConfigError::Lengths {
+      min_len: 10,
+      max_len: 8
+    }),
+    config.build().map(drop)
+  );
+  config.max_len = Some(0);
+  assert!(config.build().is_ok());
+}
+
+#[test]
+fn applies_max_len_and_context() {
+  let config = json(r#"{ "variant": "b32", "salt": "salt", "max_len": 6, "context": "user" }"#);
+  let ids = config.build().unwrap();
+  let expected = HashIdB32::with_salt(b"salt").with_max_len(6).with_context(b"user");
+  assert_eq!(expected.encode([5]), ids.encode([5]));
+  assert_eq!(expected.encode_buf([u64::MAX]), None);
+  assert_eq!(ids.encode_buf([u64::MAX]), None);
+}
+
+#[test]
+fn build_as_checks_the_variant() {
+  let config = HashIdConfig::new(Variant::B32, "salt");
+  let ids = config.build_as::<HashIdB32>().unwrap();
+  assert_eq!(HashIdB32::with_salt(b"salt").encode([9]), ids.encode([9]));
+  assert_eq!(
+    Some(ConfigError::Variant {
+      expected: Variant::B64,
+      found: Variant::B32
+    }),
+    config.build_as::<HashIdB64>().err()
+  );
+}
+
+#[test]
+fn from_vars() {
+  let vars = HashMap::from([
+    ("APP_SALT", "73616C74"),
+    ("APP_SALT_ENCODING", "HEX"),
+    ("APP_VARIANT", "qr"),
+    ("APP_MIN_LEN", " 12 "),
+    ("APP_CONTEXT", "order"),
+  ]);
+  let lookup = |name: &str| vars.get(name).map(|v| v.to_string());
+  let config = HashIdConfig::from_vars("APP_", lookup).unwrap();
+  assert_eq!(Variant::Qr, config.variant);
+  assert_eq!(Some(12), config.min_len);
+  assert_eq!(None, config.max_len);
+  assert_eq!(Some("order".into()), config.context);
+  assert_eq!(b"salt".to_vec(), config.salt_bytes().unwrap());
+
+  assert_eq!(
+    Some(ConfigError::Missing("OTHER_SALT".into())),
+    HashIdConfig::from_vars("OTHER_", lookup).err()
+  );
+  let bad = |name: &str| match name {
+    | "BAD_SALT" => Some("salt".into()),
+    | "BAD_MIN_LEN" => Some("ten".into()),
+    | _ => None,
+  };
+  assert_eq!(
+    Some(ConfigError::Invalid("BAD_MIN_LEN".into())),
+    HashIdConfig::from_vars("BAD_", bad).err()
+  );
+}
+
+#[test]
+fn from_env() {
+  // reads only; setting variables here would race the other tests
+  assert_eq!(
+    Some(ConfigError::Missing("HASHID_CONFIG_TEST_UNSET_SALT".into())),
+    HashIdConfig::from_env_prefixed("HASHID_CONFIG_TEST_UNSET_").err()
+  );
+}
+
+#[test]
+fn debug_redacts_the_salt() {
+  let debug = format!("{:?}", HashIdConfig::new(Variant::Qr, "top secret"));
+  assert!(!debug.contains("top secret"));
+  assert!(debug.contains("Qr"));
+}
@@ -0,0 +1,56 @@
+use hashid_stack::prelude::*;
+
+#[test]
+fn contexts_do_not_cross() {
+  let ids = HashIdDefault::with_salt(b"shared salt");
+  let users = ids.with_context(b"user");
+  let orders = ids.with_context(b"order");
+  for v in 0..1000u64 {
+    let user = users.encode([v]);
+    assert_eq!([v], users.decode(&user).unwrap());
+    assert_ne!(user, orders.encode([v]));
+    assert_ne!(user, ids.encode([v]));
+    // plain IDs are dense, so some decode, but never to the same number
+    if let Ok(order) = orders.decode::<1>(&user) {
+      assert_ne!([v], order);
+    }
+  }
+}
+
+#[test]
+fn contexts_with_a_mac_reject_each_other() {
+  let ids = HashIdDefault::with_salt(b"shared salt").with_mac(*b"sixteen byte key", 4);
+  let users = ids.with_context(b"user");
+  let orders = ids.with_context(b"order");
+  for v in 0..1000u64 {
+    assert!(orders.decode::<1>(users.encode([v])).is_err());
+  }
+}
+
+#[test]
+fn context_survives_other_settings() {
+  let plain = HashIdB32::with_salt_and_len(b"salt", 10).with_context(b"invoice");
+  let ids = plain.with_fpe().with_mac(*b"sixteen byte key", 4).with_check_char();
+  // setting the context last gives the same encoder
+  let late = HashIdB32::with_salt_and_len(b"salt", 10)
+    .with_fpe()
+    .with_mac(*b"sixteen byte key", 4)
+    .with_check_char()
+    .with_context(b"invoice");
+  for v in [0, 7, 1 << 33, u64::MAX] {
+    let id = ids.encode([v, 3]);
+    assert_eq!(id, late.encode([v, 3]));
+    assert_eq!([v, 3], ids.decode(&id).unwrap());
+  }
+  // replacing the context
+  let other = ids.with_context(b"receipt");
+  assert_eq!(ids.with_context(b"invoice").encode([9]), ids.encode([9]));
+  assert_ne!(other.encode([9]), ids.encode([9]));
+}
+
+#[test]
+fn empty_context_still_separates() {
+  let ids = HashIdB64::with_salt(b"salt");
+  assert_ne!(ids.encode([1, 2, 3]), ids.with_context(b"").encode([1, 2, 3]));
+  assert!(format!("{:?}", ids.with_context(b"x")).contains("context: true"));
+}
@@ -0,0 +1,48 @@
+use hashid_stack::prelude::*;
+
+const HOUR: u64 = 3600;
+const SKEW: u64 = 60;
+
+#[test]
+fn ttl_is_enforced() {
+  let ids = HashIdQr::with_salt_and_len(b"reset links", 10).with_mac(*b"0123456789abcdef", 4);
+  let issued = 1_700_000_000;
+  let link = ids.encode_expiring([7, 8], issued).unwrap();
+
+  assert_eq!(([7, 8], issued), ids.decode_expiring::<2>(&link, issued, HOUR, SKEW).unwrap());
+  assert_eq!(([7, 8], issued), ids.decode_expiring::<2>(&link, issued + HOUR, HOUR, SKEW).unwrap());
+  // issued "in the future" by a service with a fast clock
+  assert!(ids.decode_expiring::<2>(&link, issued - 10, HOUR, SKEW).is_ok());
+  assert!(matches!(
+    ids.decode_expiring::<2>(&link, issued + HOUR + 1, HOUR, SKEW),
+    Err(DecodeErr::Expired(t)) if t == issued
+  ));
+}
+
+#[test]
+fn errors_count_caller_values() {
+  let ids = HashIdB32::with_salt(b"reset links");
+  let link = ids.encode_expiring([1, 2, 3], 0).unwrap();
+  assert!(matches!(ids.decode_expiring::<2>(&link, 0, 1, 0), Err(DecodeErr::Items(2, 3))));
+  assert!(matches!(ids.decode_expiring::<1>(ids.encode([5]), 0, 1, 0), Err(DecodeErr::Items(1, 0))));
+}
+
+#[test]
+fn future_issue_times_are_bounded() {
+  let ids = HashIdQr::with_salt_and_len(b"reset links", 10).with_mac(*b"0123456789abcdef", 4);
+  let now = 1_700_000_000;
+  let link = ids.encode_expiring([7], now + SKEW).unwrap();
+  assert!(ids.decode_expiring::<1>(&link, now, HOUR, SKEW).is_ok());
+  let link = ids.encode_expiring([7], now + SKEW + 1).unwrap();
+  assert!(matches!(
+    ids.decode_expiring::<1>(&link, now, HOUR, SKEW),
+    Err(DecodeErr::Future(t)) if t == now + SKEW + 1
+  ));
+  // would otherwise never expire
+  let forever = ids.encode_expiring([7], u64::MAX).unwrap();
+  assert!(matches!(
+    ids.decode_expiring::<1>(&forever, now, HOUR, SKEW),
+    Err(DecodeErr::Future(u64::MAX))
+  ));
+  assert!(ids.decode_expiring::<1>(&forever, u64::MAX - 1, HOUR, SKEW).is_ok());
+}
@@ -0,0 +1,73 @@
+use hashid_stack::{
+  any::{AnyHashId, Variant},
+  lottery, shuffle,
+  prelude::*,
+};
+use std::collections::HashSet;
+
+const KEY: &[u8; 16] = b"fingerprint key!";
+
+#[test]
+fn same_settings_same_fingerprint() {
+  let a = HashIdQr::with_salt_and_len(b"salt", 8).with_context(b"user");
+  let b = HashIdQr::with_salt_and_len(b"salt", 8).with_context(b"user");
+  assert_eq!(a.fingerprint(KEY), b.fingerprint(KEY));
+  assert_eq!(a, b);
+  let any = AnyHashId::new(Variant::Qr, b"salt", 8).unwrap();
+  assert_eq!(a.fingerprint(KEY), any.with_context(b"user").fingerprint(KEY));
+}
+
+#[test]
+fn settings_change_the_fingerprint() {
+  let base = HashIdB64::with_salt(b"salt");
+  let changed = [
+    HashIdB64::with_salt(b"other salt").fingerprint(KEY),
+    HashIdB64::with_salt_and_len(b"salt", 8).fingerprint(KEY),
+    base.with_max_len(12).fingerprint(KEY),
+    base.with_context(b"user").fingerprint(KEY),
+    base.with_check_char().fingerprint(KEY),
+    base.with_mac([7; 16], 2).fingerprint(KEY),
+    base.with_fpe().fingerprint(KEY),
+    base.randomized().fingerprint(KEY),
+    base.with_lottery::<lottery::Keyed>().fingerprint(KEY),
+    base.with_shuffle::<shuffle::Keyed>().fingerprint(KEY),
+    base.fingerprint(b"some other key!!"),
+  ];
+  let mut seen = HashSet::from([base.fingerprint(KEY)]);
+  for fingerprint in changed {
+    assert!(seen.insert(fingerprint));
+  }
+}
+
+#[test]
+fn variants_differ() {
+  let fingerprints: HashSet<_> = Variant::ALL
+    .into_iter()
+    .map(|v| AnyHashId::new(v, b"salt", None).unwrap().fingerprint(KEY))
+    .collect();
+  assert_eq!(4, fingerprints.len());
+}
+
+#[test]
+fn hex_and_debug() {
+  let ids = HashIdB32::with_salt(b"salt");
+  let hex = ids.fingerprint_hex(KEY);
+  assert_eq!(16, hex.len());
+  assert_eq!(
+    u64::from_be_bytes(ids.fingerprint(KEY)),
+    u64::from_str_radix(&hex, 16).unwrap()
+  );
+  assert!(!format!("{:?}", ids).contains("fingerprint"));
+}
+
+#[test]
+fn eq_includes_secrets() {
+  let ids = HashIdB64::with_salt(b"salt");
+  assert_ne!(ids.with_mac([1; 16], 2), ids.with_mac([2; 16], 2));
+  assert_eq!(
+    ids.with_mac([1; 16], 2).fingerprint(KEY),
+    ids.with_mac([2; 16], 2).fingerprint(KEY)
+  );
+  let set: HashSet<_> = [ids, ids, ids.with_check_char()].into_iter().collect();
+  assert_eq!(2, set.len());
+}
@@ -0,0 +1,31 @@
+use hashid_stack::prelude::*;
+
+macro_rules! round_trips {
+  ($($t:ty),*) => {$(
+    let plain = <$t>::with_salt(b"format preserving");
+    let ids = plain.with_fpe();
+    let mut samples = vec![0, 1, u64::MAX, u64::MAX - 1, 1 << 63];
+    samples.extend((0..64).map(|i| 1u64 << i));
+    samples.extend((0..64).map(|i| (1u64 << i).wrapping_sub(1)));
+    samples.extend((0..200).map(|i| i * 7919));
+    for &v in &samples {
+      let id = ids.encode([v, v / 3]);
+      assert_eq!(plain.encode([v, v / 3]).len(), id.len(), "{} {}", stringify!($t), v);
+      assert_eq!([v, v / 3], ids.decode::<2>(&id).expect(stringify!($t)));
+    }
+  )*}
+}
+
+#[test]
+fn keeps_length_and_round_trips() {
+  round_trips!(HashIdDefault, HashIdQr, HashIdB32, HashIdB64);
+}
+
+#[test]
+fn differs_from_plain() {
+  let plain = HashIdDefault::with_salt(b"format preserving");
+  let ids = plain.with_fpe();
+  let same = (1..=200u64).filter(|&v| plain.encode([v]) == ids.encode([v])).count();
+  assert!(same < 20, "{} of 200 unchanged", same);
+  assert_ne!(ids.encode([5, 5]), HashIdDefault::with_salt(b"other salt").with_fpe().encode([5, 5]));
+}
@@ -0,0 +1,71 @@
+use hashid_stack::{hashid_type, prelude::*};
+use std::collections::BTreeSet;
+
+hashid_type!(
+  /// An order
+  pub OrderId, HashIdB32, salt = b"orders", min_len = 10
+);
+hashid_type!(InvoiceId, HashIdB32, salt = b"invoices");
+
+#[test]
+fn displays_and_parses() {
+  for v in [0, 1, 99, u64::MAX] {
+    let id = OrderId(v);
+    let text = id.to_string();
+    assert!(text.len() >= 10);
+    assert_eq!(OrderId::encoder().encode([v]), text);
+    assert_eq!(id, text.parse().unwrap());
+    assert_eq!(id, OrderId::try_from(text.as_str()).unwrap());
+    assert_eq!(v, u64::from(id));
+  }
+  // different salts, different text
+  assert_ne!(OrderId(5).to_string(), InvoiceId(5).to_string());
+  assert!(InvoiceId(5).to_string().len() < 10);
+}
+
+#[test]
+fn rejects_what_decode_rejects() {
+  assert!(matches!("".parse::<OrderId>(), Err(DecodeErr::Hash)));
+  let two = OrderId::encoder().encode([1, 2]);
+  assert!(matches!(two.parse::<OrderId>(), Err(DecodeErr::Items(1, 2))));
+}
+
+#[test]
+fn orders_by_number() {
+  let ids: BTreeSet<_> = [3, 1, 2].into_iter().map(OrderId::from).collect();
+  assert_eq!(vec![OrderId(1), OrderId(2), OrderId(3)], ids.into_iter().collect::<Vec<_>>());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serializes_as_the_id() {
+  use hashid_stack::serde::As;
+
+  #[derive(serde::Serialize, serde::Deserialize)]
+  struct Row {
+    id: OrderId,
+    #[serde(with = "As::<OrderId>")]
+    raw: u64,
+  }
+  let json = serde_json::to_string(&Row { id: OrderId(7), raw: 8 }).unwrap();
+  assert_eq!(json, format!(r#"{{"id":"{}","raw":"{}"}}"#, OrderId(7), OrderId(8)));
+  let row: Row = serde_json::from_str(&json).unwrap();
+  assert_eq!((OrderId(7), 8), (row.id, row.raw));
+}
+
+mod shadowed {
+  #![allow(dead_code)]
+  use hashid_stack::{hashid_type, prelude::*};
+
+  type Result<T> = core::result::Result<T, ()>;
+  type Option = ();
+  trait From {}
+
+  hashid_type!(pub ShadowId, HashIdQr, salt = b"shadowed");
+
+  #[test]
+  fn ignores_local_names() {
+    let text = ShadowId(3).to_string();
+    assert_eq!(Ok(ShadowId(3)), text.parse::<ShadowId>().map_err(drop) as Result<_>);
+  }
+}
@@ -0,0 +1,22 @@
+#![cfg(feature = "kdf")]
+use hashid_stack::{kdf, prelude::*};
+
+#[test]
+fn known_answer() {
+  // sha256(b"hashid-stack passphrase salt v1\0correct horse battery staple")
+  let expected = [
+    0x7d, 0xa1, 0x56, 0x6b, 0x22, 0x27, 0x24, 0xbb, 0x85, 0x2f, 0xfd, 0xbd, 0x22, 0xa8, 0xb5, 0x6b,
+    0x07, 0x2e, 0x2e, 0x38, 0xba, 0x22, 0xe2, 0x20, 0x7e, 0xf7, 0x95, 0xcb, 0x4c, 0x3d, 0x83, 0x48,
+  ];
+  assert_eq!(expected, kdf::derive_salt("correct horse battery staple"));
+}
+
+#[test]
+fn passphrase_encoders_agree() {
+  let a = HashIdQr::with_passphrase_and_len("tenant passphrase", 8);
+  let b = HashIdQr::with_passphrase_and_len(String::from("tenant passphrase"), 8);
+  let c = HashIdQr::with_passphrase_and_len("another passphrase", 8);
+  assert_eq!(a.encode([99, 100]), b.encode([99, 100]));
+  assert_ne!(a.encode([99, 100]), c.encode([99, 100]));
+  assert_eq!([99, 100], b.decode::<2>(a.encode([99, 100])).unwrap());
+}
@@ -0,0 +1,47 @@
+use hashid_stack::prelude::*;
+
+#[test]
+fn rotated_ids_keep_decoding() {
+  let v1 = HashIdDefault::with_salt_slice(b"2021").unwrap();
+  let v2 = HashIdDefault::with_salt_slice(b"2022 rotation").unwrap();
+  let v3 = HashIdDefault::with_salt_slice(b"current").unwrap();
+  let ring = KeyRing::new(v3, [v2, v1]);
+  assert_eq!(3, ring.len());
+
+  assert_eq!(v3.encode([42, 7]), ring.encode([42, 7]));
+  for (idx, key) in [v3, v2, v1].iter().enumerate() {
+    let (out, found) = ring.decode::<2>(key.encode([42, 7])).expect("key in ring");
+    assert_eq!([42, 7], out);
+    assert_eq!(idx, found);
+    assert_eq!(key.encode([1]), ring.get(found).unwrap().encode([1]));
+  }
+}
+
+#[test]
+fn unknown_salt_is_rejected() {
+  let ring = KeyRing::new(HashIdB32::with_salt(b"new"), [HashIdB32::with_salt(b"old")]);
+  let stranger = HashIdB32::with_salt(b"foreign");
+  assert!(ring.decode::<1>(stranger.encode([123456])).is_err());
+  assert!(ring.get(2).is_none());
+}
+
+#[test]
+fn old_ids_never_decode_to_other_records() {
+  let old = HashIdB64::with_salt(b"old salt");
+  let plain = KeyRing::new(HashIdB64::with_salt(b"new salt"), [old]);
+  let tagged = KeyRing::new(HashIdB64::with_salt(b"new salt").with_mac(*b"generation 2 key", 4), [old]);
+  let mut ambiguous = 0;
+  for v in 0..5000u64 {
+    let id = old.encode([v]);
+    match plain.decode::<1>(&id) {
+      | Ok(found) => assert_eq!(([v], 1), found),
+      | Err(err) => {
+        assert!(matches!(err, DecodeErr::Ambiguous(0, 1)), "{}", err);
+        ambiguous += 1;
+      }
+    }
+    assert_eq!(([v], 1), tagged.decode::<1>(&id).unwrap());
+    assert_eq!(([v], 0), tagged.decode::<1>(tagged.encode([v])).unwrap());
+  }
+  assert!(ambiguous > 0);
+}
@@ -0,0 +1,49 @@
+use hashid_stack::{lottery::Keyed, prelude::*, transcode::transcode_with};
+use std::collections::HashMap;
+
+fn spread(first_chars: impl Iterator<Item = u8>) -> (usize, usize, usize) {
+  let mut counts = HashMap::new();
+  for c in first_chars {
+    *counts.entry(c).or_insert(0usize) += 1;
+  }
+  (counts.len(), *counts.values().min().unwrap(), *counts.values().max().unwrap())
+}
+
+#[test]
+fn keyed_spreads_uniformly() {
+  let classic = HashIdB64::with_salt(b"lottery");
+  let keyed = classic.with_lottery::<Keyed>();
+  let real = <HashIdB64 as HashId>::REAL - <HashIdB64 as HashId>::GUARDS;
+
+  let (n, min, max) = spread((0..20_000u64).map(|v| keyed.encode([v, 1]).as_bytes()[0]));
+  assert_eq!(real, n);
+  assert!(max < min * 2, "keyed spread {}..{}", min, max);
+
+  // classic lotteries repeat every 100 values
+  let same_first = |hi: &dyn Fn(u64) -> String| {
+    (0..1000u64).filter(|&v| hi(v).as_bytes()[0] == hi(v + 100).as_bytes()[0]).count()
+  };
+  assert_eq!(1000, same_first(&|v| classic.encode([v]).to_string()));
+  assert!(same_first(&|v| keyed.encode([v]).to_string()) < 100);
+}
+
+#[test]
+fn keyed_round_trips() {
+  let keyed = HashIdQr::with_salt_and_len(b"lottery", 9).with_lottery::<Keyed>().with_check_char();
+  for v in [0, 1, 99, 100, 101, u64::MAX] {
+    let id = keyed.encode([v, v / 2]);
+    assert_eq!([v, v / 2], keyed.decode::<2>(&id).unwrap());
+  }
+  // the classic instance can still read them, but won't call them canonical
+  let classic = HashIdQr::with_salt_and_len(b"lottery", 9).with_check_char();
+  let id = keyed.encode([12345, 1]);
+  assert_eq!([12345, 1], classic.decode_fast::<2>(&id).unwrap());
+}
+
+#[test]
+fn transcodes_to_keyed() {
+  let classic = HashIdQr::with_salt(b"lottery");
+  let keyed = classic.with_lottery::<Keyed>();
+  let moved = transcode_with::<_, _, 7, 7, 1, _, _, _, _>(&classic, &keyed, classic.encode([77])).unwrap();
+  assert_eq!(keyed.encode([77]), moved.as_str());
+}
@@ -0,0 +1,35 @@
+use hashid_stack::prelude::*;
+
+const KEY: [u8; 16] = *b"0123456789abcdef";
+
+#[test]
+fn round_trips_with_tag() {
+  let plain = HashIdQr::with_salt_and_len(b"salt", 10);
+  let ids = plain.with_mac(KEY, 5);
+  for data in [[0u64, 1], [1, 2], [u64::MAX, 12345]] {
+    let id = ids.encode(data);
+    let untagged = plain.encode(data);
+    assert_eq!(untagged.len() + 5, id.len());
+    assert!(id.starts_with(untagged.as_str()));
+    assert_eq!(data, ids.decode::<2>(&id).unwrap());
+    assert_eq!(data, ids.decode_fast::<2>(&id).unwrap());
+  }
+}
+
+#[test]
+fn rejects_tampering() {
+  let ids = HashIdB64::with_salt(b"salt").with_mac(KEY, 4);
+  let other_key = HashIdB64::with_salt(b"salt").with_mac(*b"fedcba9876543210", 4);
+  let id = ids.encode([1000]);
+  assert!(matches!(other_key.decode::<1>(&id), Err(DecodeErr::Mac)));
+  assert!(matches!(ids.decode::<1>("ab"), Err(DecodeErr::Mac)));
+
+  // flip every character in turn
+  let mut bytes = id.as_bytes().to_vec();
+  for i in 0..bytes.len() {
+    let orig = bytes[i];
+    bytes[i] = if orig == b'A' { b'B' } else { b'A' };
+    assert!(ids.decode_fast::<1>(&bytes).map_or(true, |v| v != [1000]), "{}", i);
+    bytes[i] = orig;
+  }
+}
@@ -0,0 +1,107 @@
+use hashid_stack::{
+  any::{AnyHashId, Variant},
+  codec::HashIdCodec,
+  prefixed::Prefixed,
+  prelude::*,
+};
+
+#[test]
+fn round_trip() {
+  let ids = HashIdB64::with_salt_and_len(b"users", 8);
+  let users = Prefixed::new("usr", ids).unwrap();
+  let id = users.encode([1, 2, 3]);
+  assert_eq!(format!("usr_{}", ids.encode([1, 2, 3])), id);
+  assert_eq!([1, 2, 3], users.decode(&id).unwrap());
+  assert_eq!(&*id, users.encode_buf([1, 2, 3]).unwrap().as_str());
+  assert_eq!(("usr", b'_'), (users.prefix(), users.separator()));
+}
+
+#[test]
+fn wrong_prefix() {
+  let ids = HashIdQr::with_salt(b"salt");
+  let users = Prefixed::new("usr", ids).unwrap();
+  let orders = Prefixed::new("ord", ids).unwrap();
+  let id = orders.encode([9]);
+  match users.decode::<1>(&id) {
+    | Err(DecodeErr::WrongPrefix { expected, found }) => {
+      assert_eq!("usr", expected);
+      assert_eq!(b"ord", found.as_slice());
+    }
+    | other => panic!("{:?}", other),
+  }
+  assert_eq!(
+    "expected prefix \"usr\", found \"ord\"",
+    users.decode::<1>(&id).unwrap_err().to_string()
+  );
+  // missing separator, or no prefix at all
+  let bare = ids.encode([9]);
+  for input in [format!("usr{}", bare), bare.to_string()] {
+    assert!(matches!(users.decode::<1>(input), Err(DecodeErr::WrongPrefix { .. })));
+  }
+  match users.decode::<1>("a_very_long_prefix_indeed_abc") {
+    | Err(DecodeErr::WrongPrefix { found, .. }) => assert_eq!(b"a", found.as_slice()),
+    | other => panic!("{:?}", other),
+  }
+  match users.decode::<1>("averylongprefixindeed") {
+    | Err(DecodeErr::WrongPrefix { found, .. }) => {
+      assert_eq!(b"averylongprefixi", found.as_slice())
+    }
+    | other => panic!("{:?}", other),
+  }
+}
+
+#[test]
+fn inner_errors_pass_through() {
+  let users = Prefixed::new("usr", HashIdB64::with_salt(b"salt").with_check_char()).unwrap();
+  let id = users.encode([5]);
+  let mut bad = id.to_string().into_bytes();
+  let last = bad.len() - 1;
+  bad[last] = if bad[last] == b'a' { b'b' } else { b'a' };
+  assert!(matches!(users.decode::<1>(&bad), Err(DecodeErr::Check)));
+}
+
+#[test]
+fn separator_and_validation() {
+  let ids = HashIdB32::with_salt(b"salt");
+  let users = Prefixed::new("usr", ids).unwrap().with_separator(b'-').unwrap();
+  let id = users.encode([4]);
+  assert!(id.starts_with("usr-"));
+  assert_eq!([4], users.decode(&id).unwrap());
+
+  assert!(Prefixed::new("", ids).is_none());
+  assert!(Prefixed::new("us r", ids).is_none());
+  assert!(Prefixed::new("us_r", ids).is_none());
+  assert!(Prefixed::new("usé", ids).is_none());
+  assert!(Prefixed::new("usr", ids).unwrap().with_separator(b' ').is_none());
+  assert!(Prefixed::new("usr", ids).unwrap().with_separator(b'r').is_none());
+}
+
+#[test]
+fn prefix_as_context() {
+  let ids = HashIdB64::with_salt(b"salt");
+  let users = Prefixed::new("usr", ids).unwrap().with_context();
+  let orders = Prefixed::new("ord", ids).unwrap().with_context();
+  let (user, order) = (users.encode([42]), orders.encode([42]));
+  assert_ne!(user[4..], order[4..]);
+  assert_eq!(ids.with_context(b"usr").encode([42]), user[4..]);
+
+  // swapping the prefix doesn't give the same number back
+  let swapped = format!("ord_{}", &user[4..]);
+  assert_ne!(Some([42]), orders.decode::<1>(&swapped).ok());
+
+  let any = AnyHashId::new(Variant::B64, b"salt", None).unwrap();
+  let any = Prefixed::new("usr", any).unwrap().with_context();
+  assert_eq!(user, any.encode([42]));
+}
+
+#[test]
+fn as_codec() {
+  let users = Prefixed::new("usr", HashIdQr::with_salt_and_len(b"salt", 6)).unwrap();
+  let codec: Box<dyn HashIdCodec> = Box::new(users);
+  let id = codec.encode_id(&[1, 2]).unwrap();
+  assert!(id.starts_with("usr_"));
+  let mut out = [0; 2];
+  codec.decode_into(id.as_bytes(), &mut out).unwrap();
+  assert_eq!([1, 2], out);
+  assert_eq!(("qr", 10), (codec.variant(), codec.min_len()));
+}
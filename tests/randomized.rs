@@ -0,0 +1,46 @@
+use hashid_stack::prelude::*;
+use std::collections::HashSet;
+
+/// xorshift64, good enough to pick lotteries in tests
+fn xorshift(mut seed: u64) -> impl FnMut() -> u64 {
+  move || {
+    seed ^= seed << 13;
+    seed ^= seed >> 7;
+    seed ^= seed << 17;
+    seed
+  }
+}
+
+macro_rules! random_forms {
+  ($($t:ty),*) => {$(
+    let ids = <$t>::with_salt_and_len(b"unlinkable", 12).with_check_char().randomized();
+    let strict = <$t>::with_salt_and_len(b"unlinkable", 12).with_check_char();
+    let mut rng = xorshift(0x9e3779b97f4a7c15);
+    let mut seen = HashSet::new();
+    for _ in 0..64 {
+      let id = ids.encode_random([314, 159], &mut rng).unwrap();
+      assert_eq!([314, 159], ids.decode::<2>(&id).expect(stringify!($t)));
+      assert_eq!([314, 159], strict.decode_fast::<2>(&id).expect(stringify!($t)));
+      seen.insert(id);
+    }
+    assert!(seen.len() > 8, "{}: only {} forms", stringify!($t), seen.len());
+    // deterministic IDs are one of the valid forms
+    assert_eq!([314, 159], ids.decode::<2>(strict.encode([314, 159])).unwrap());
+  )*}
+}
+
+#[test]
+fn decode_accepts_every_form() {
+  random_forms!(HashIdDefault, HashIdQr, HashIdB32, HashIdB64);
+}
+
+#[test]
+fn strict_mode_rejects_random_forms() {
+  let ids = HashIdB64::with_salt(b"unlinkable");
+  let mut rng = xorshift(7);
+  let rejected = (0..32)
+    .map(|_| ids.encode_random([99], &mut rng).unwrap())
+    .filter(|id| ids.decode::<1>(id).is_err())
+    .count();
+  assert!(rejected > 0);
+}
@@ -0,0 +1,107 @@
+use hashid_stack::{
+  any::{AnyHashId, Variant},
+  prelude::*,
+  registry::{Registry, RegistryError},
+};
+
+fn registry() -> Registry {
+  let mut registry = Registry::new();
+  registry.register("user", AnyHashId::new(Variant::B64, b"users", 10).unwrap()).unwrap();
+  registry.register("order", AnyHashId::new(Variant::B32, b"orders", 10).unwrap()).unwrap();
+  registry.register("invoice", HashIdQr::with_salt_slice(b"invoices").unwrap()).unwrap();
+  registry
+}
+
+#[test]
+fn looks_up_by_name() {
+  let registry = registry();
+  assert_eq!(3, registry.len());
+  assert_eq!(vec!["user", "order", "invoice"], registry.names().collect::<Vec<_>>());
+  assert_eq!(Variant::B32, registry.get("order").unwrap().variant());
+  assert!(registry.get("missing").is_none());
+  assert_eq!(
+    HashIdQr::with_salt(b"invoices").encode([3]),
+    registry.get("invoice").unwrap().encode([3])
+  );
+}
+
+#[test]
+fn decode_any_reports_the_encoder() {
+  let registry = registry();
+  for name in ["user", "order", "invoice"] {
+    let id = registry.get(name).unwrap().encode([1234, 5]);
+    let (found, values) = registry.decode_any::<2>(&id).unwrap();
+    assert_eq!((name, [1234, 5]), (found, values));
+  }
+  assert!(registry.decode_any::<1>("!!").is_none());
+}
+
+#[test]
+fn decode_any_never_picks_the_wrong_encoder() {
+  let a = HashIdB64::with_salt_slice(b"a").unwrap();
+  let b = HashIdB64::with_salt_slice(b"b").unwrap();
+  let mut plain = Registry::new();
+  plain.register("a", a).unwrap();
+  plain.register("b", b).unwrap();
+  let mut tagged = Registry::new();
+  tagged.register("a", a.with_mac(*b"sixteen byte key", 4)).unwrap();
+  tagged.register("b", b).unwrap();
+  for v in 0..3000u64 {
+    let id = b.encode([v]);
+    if let Some(found) = plain.decode_any::<1>(&id) {
+      assert_eq!(("b", [v]), found);
+    }
+    assert_eq!(Some(("b", [v])), tagged.decode_any::<1>(&id));
+  }
+}
+
+#[test]
+fn rejects_duplicates() {
+  let mut registry = registry();
+  let again = HashIdB32::with_salt_slice(b"other").unwrap();
+  assert_eq!(Err(RegistryError::Duplicate("user".into())), registry.register("user", again));
+  assert_eq!(3, registry.len());
+
+  let ids = AnyHashId::from(HashIdB32::with_salt_slice(b"x").unwrap().with_check_char());
+  registry.register("checked", ids).unwrap();
+  assert_eq!(Some("checked"), registry.names().last());
+}
+
+#[cfg(feature = "config")]
+#[test]
+fn builds_from_configs() {
+  use hashid_stack::config::{ConfigError, HashIdConfig};
+  let mut user = HashIdConfig::new(Variant::B64, "users");
+  user.min_len = Some(10);
+  user.context = Some("user".into());
+  let invoice = HashIdConfig::new(Variant::Qr, "invoices");
+  let mut registry = Registry::from_configs([("user", &user), ("invoice", &invoice)]).unwrap();
+  let expected = HashIdB64::with_salt_and_len(b"users", 10).with_context(b"user");
+  assert_eq!(expected.encode([4]), registry.get("user").unwrap().encode([4]));
+
+  assert_eq!(
+    Err(RegistryError::Duplicate("user".into())),
+    registry.register_config("user", &invoice)
+  );
+  let long = HashIdConfig::new(Variant::B32, "x".repeat(65));
+  assert_eq!(
+    Err(RegistryError::Config("long".into(), ConfigError::SaltTooLong(65))),
+    registry.register_config("long", &long)
+  );
+  assert_eq!(2, registry.len());
+}
+
+#[test]
+fn debug_hides_salts() {
+  assert!(!format!("{:?}", registry()).contains("users"));
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn zeroize_wipes_every_encoder() {
+  use zeroize::Zeroize;
+  let mut registry = registry();
+  registry.zeroize();
+  let debug = format!("{:?}", registry);
+  assert_eq!(3, debug.matches("0 bytes").count(), "{}", debug);
+}
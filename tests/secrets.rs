@@ -0,0 +1,35 @@
+use hashid_stack::prelude::*;
+
+#[test]
+fn debug_redacts_salt() {
+  let hi = HashIdB64::with_salt_and_len(b"super secret salt", 6);
+  let out = format!("{:?} {:#?}", hi, KeyRing::new(hi, [hi]));
+  assert!(!out.contains("super secret salt"), "{}", out);
+  assert!(!out.contains("115, 117, 112"), "{}", out);
+  assert!(out.contains("HashIdB64"), "{}", out);
+  assert!(out.contains("17 bytes"), "{}", out);
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn zeroize_wipes_secrets() {
+  use zeroize::Zeroize;
+  let mut hi = HashIdB64::with_salt(b"super secret salt");
+  hi.zeroize();
+  assert!(format!("{:?}", hi).contains("0 bytes"));
+  let _wiped_on_drop = zeroize::Zeroizing::new(HashIdB64::with_salt(b"super secret salt"));
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn zeroize_wipes_any_variant() {
+  use hashid_stack::any::{AnyHashId, Variant};
+  use zeroize::Zeroize;
+  for variant in Variant::ALL {
+    let mut ids = AnyHashId::new(variant, b"super secret salt", 8).unwrap();
+    ids.zeroize();
+    assert!(format!("{:?}", ids).contains("0 bytes"));
+    assert_ne!(AnyHashId::new(variant, b"super secret salt", 8).unwrap(), ids);
+  }
+  let _wiped_on_drop = zeroize::Zeroizing::new(AnyHashId::new(Variant::B32, b"salt", None).unwrap());
+}
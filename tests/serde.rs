@@ -0,0 +1,90 @@
+#![cfg(feature = "serde")]
+use hashid_stack::{
+  encoded::EncodedId,
+  prelude::*,
+  serde::{As, Source},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+static ORDERS: LazyLock<HashID<B32, 6>> =
+  LazyLock::new(|| B32::with_salt_and_len(b"orders", 8).with_check_char());
+
+struct Orders;
+impl Source for Orders {
+  fn encode(values: &[u64]) -> Option<EncodedId> {
+    ORDERS.encode_buf(values)
+  }
+  fn decode(input: &[u8], out: &mut [u64]) -> Result<(), DecodeErr<0>> {
+    ORDERS.decode_into(input, out)
+  }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Order {
+  #[serde(with = "As::<Orders>")]
+  id: u64,
+  #[serde(with = "As::<Orders>")]
+  line: (u64, u64),
+  #[serde(with = "As::<Orders>")]
+  batch: [u64; 3],
+  #[serde(with = "As::<Orders>", default)]
+  parent: Option<u64>,
+  #[serde(with = "As::<Orders>")]
+  split: Option<(u64, u64, u64)>,
+}
+
+#[test]
+fn round_trips_through_json() {
+  let order = Order {
+    id: 42,
+    line: (42, 3),
+    batch: [1, 2, 3],
+    parent: Some(41),
+    split: None,
+  };
+  let json = serde_json::to_value(&order).unwrap();
+  assert_eq!(json["id"], ORDERS.encode([42]).to_string());
+  assert_eq!(json["line"], ORDERS.encode([42, 3]).to_string());
+  assert_eq!(json["batch"], ORDERS.encode([1, 2, 3]).to_string());
+  assert_eq!(json["parent"], ORDERS.encode([41]).to_string());
+  assert!(json["split"].is_null());
+  assert_eq!(order, serde_json::from_value(json).unwrap());
+}
+
+#[test]
+fn missing_option_defaults_to_none() {
+  let json = format!(
+    r#"{{"id":"{}","line":"{}","batch":"{}","split":"{}"}}"#,
+    ORDERS.encode([1]),
+    ORDERS.encode([1, 2]),
+    ORDERS.encode([1, 2, 3]),
+    ORDERS.encode([4, 5, 6]),
+  );
+  let order: Order = serde_json::from_str(&json).unwrap();
+  assert_eq!(None, order.parent);
+  assert_eq!(Some((4, 5, 6)), order.split);
+}
+
+#[test]
+fn errors_say_what_went_wrong() {
+  let wrong_count = format!(
+    r#"{{"id":"{}","line":"{}","batch":"{}","split":null}}"#,
+    ORDERS.encode([1, 2]),
+    ORDERS.encode([1, 2]),
+    ORDERS.encode([1, 2, 3]),
+  );
+  let err = serde_json::from_str::<Order>(&wrong_count).unwrap_err().to_string();
+  assert!(err.contains("invalid hash ID: expected 1 values, found 2"), "{}", err);
+
+  let mut typo = ORDERS.encode([1]).to_string();
+  typo.pop();
+  typo.push(if typo.ends_with('A') { 'B' } else { 'A' });
+  let bad = wrong_count.replacen(&ORDERS.encode([1, 2]).to_string(), &typo, 1);
+  let err = serde_json::from_str::<Order>(&bad).unwrap_err().to_string();
+  assert!(err.contains("invalid hash ID"), "{}", err);
+
+  let not_a_string = r#"{"id":7}"#;
+  let err = serde_json::from_str::<Order>(not_a_string).unwrap_err().to_string();
+  assert!(err.contains("a hash ID string"), "{}", err);
+}
@@ -0,0 +1,44 @@
+use hashid_stack::{
+  shuffle::{Consistent, Keyed, Shuffle},
+  prelude::*,
+};
+
+#[test]
+fn keyed_shuffle_is_a_permutation() {
+  for key in [&b""[..], b"a", b"some longer key"] {
+    let mut values: Vec<u8> = (0..64).collect();
+    Keyed::shuffle(&mut values, key);
+    let mut sorted = values.clone();
+    sorted.sort_unstable();
+    assert_eq!((0..64).collect::<Vec<u8>>(), sorted);
+  }
+  let mut a: Vec<u8> = (0..64).collect();
+  let mut b = a.clone();
+  Keyed::shuffle(&mut a, b"key 1");
+  Keyed::shuffle(&mut b, b"key 2");
+  assert_ne!(a, b);
+}
+
+#[test]
+fn consistent_is_the_default() {
+  let ids = HashIdDefault::with_salt(b"this is my salt");
+  let same = ids.with_shuffle::<Consistent>();
+  assert_eq!(ids.encode([12345]), same.encode([12345]));
+}
+
+#[test]
+fn keyed_round_trips_for_all_variants() {
+  macro_rules! check {
+    ($($v:ty),*) => {$({
+      let consistent = <$v>::with_salt_and_len(b"shuffle", 8).with_check_char();
+      let keyed = consistent.with_shuffle::<Keyed>();
+      for v in [0, 1, 99, 1 << 40, u64::MAX] {
+        let id = keyed.encode([v, 7]);
+        assert!(id.len() >= 8);
+        assert_eq!([v, 7], keyed.decode::<2>(&id).unwrap());
+        assert_ne!(consistent.encode([v, 7]), id);
+      }
+    })*};
+  }
+  check!(HashIdDefault, HashIdB32, HashIdB64, HashIdQr);
+}
@@ -0,0 +1,37 @@
+use hashid_stack::prelude::*;
+
+fn suggestions<const OUT: usize>(ids: &HashID<HashIdDefault, 7>, input: &str) -> Vec<String> {
+  ids
+    .suggest::<OUT>(input)
+    .map(|s| s.to_string())
+    .collect()
+}
+
+#[test]
+fn finds_single_edits() {
+  let ids = HashIdDefault::with_salt_and_len(b"support", 6).with_check_char();
+  let id = ids.encode([48_151_623]).to_string();
+  let b = id.as_bytes();
+
+  let swapped = {
+    let mut s = b.to_vec();
+    let i = (1..s.len()).find(|&i| s[i - 1] != s[i]).unwrap();
+    s.swap(i - 1, i);
+    String::from_utf8(s).unwrap()
+  };
+  let dropped = format!("{}{}", &id[..2], &id[3..]);
+  let doubled = format!("{}{}", &id[..3], &id[2..]);
+  let replaced = format!("{}{}{}", &id[..1], if b[1] == b'x' { 'y' } else { 'x' }, &id[2..]);
+
+  for typo in [swapped, dropped, doubled, replaced] {
+    assert!(ids.decode::<1>(&typo).is_err(), "{}", typo);
+    assert!(suggestions::<1>(&ids, &typo).contains(&id), "{} not suggested for {}", id, typo);
+  }
+}
+
+#[test]
+fn nothing_for_garbage() {
+  let ids = HashIdDefault::with_salt(b"support").with_check_char();
+  assert!(suggestions::<1>(&ids, "").is_empty());
+  assert_eq!(0, ids.suggest::<1>("x".repeat(500)).count());
+}
@@ -0,0 +1,33 @@
+use hashid_stack::prelude::*;
+use hashid_stack::transcode::{transcode, transcode_all};
+
+#[test]
+fn moves_between_variants() {
+  let from = HashIdQr::with_salt_and_len(b"legacy", 10);
+  let to = HashIdB64::with_salt_slice(b"modern").unwrap();
+  let stored: Vec<_> = (1..50u64).map(|i| from.encode([i, i * 1000])).collect();
+  for (i, res) in transcode_all::<_, _, 6, 64, 2, _>(&from, &to, &stored).enumerate() {
+    let moved = res.expect("valid input");
+    let values: [u64; 2] = to.decode(moved).unwrap();
+    assert_eq!([i as u64 + 1, (i as u64 + 1) * 1000], values);
+  }
+}
+
+#[test]
+fn reports_bad_entries() {
+  let from = HashIdDefault::with_salt(b"a");
+  let to = HashIdDefault::with_salt(b"b");
+  let id = from.encode([5, 6]);
+  let res = transcode::<_, _, 1, 1, 1>(&from, &to, &id);
+  assert!(matches!(res, Err(DecodeErr::Items(1, 2))));
+  assert!(transcode::<_, _, 1, 1, 2>(&from, &to, to.encode([5, 6])).is_err());
+}
+
+#[test]
+fn reports_ids_the_target_cannot_fit() {
+  let from = HashIdDefault::with_salt(b"a");
+  let to = HashIdB32::with_salt(b"b").with_max_len(6);
+  let res = transcode::<_, _, 1, 1, 1>(&from, &to, from.encode([u64::MAX]));
+  assert!(matches!(res, Err(DecodeErr::Encode)));
+  assert!(transcode::<_, _, 1, 1, 1>(&from, &to, from.encode([5])).is_ok());
+}
@@ -0,0 +1,47 @@
+use hashid_stack::prelude::*;
+
+#[test]
+fn tweaks_round_trip_and_differ() {
+  let ids = HashIdDefault::with_salt_and_len(b"shared", 6).with_check_char();
+  for v in [0, 1, 500, 1 << 40, u64::MAX] {
+    let a = ids.encode_with_tweak([v, 9], b"tenant-a").unwrap();
+    let b = ids.encode_with_tweak([v, 9], b"tenant-b").unwrap();
+    assert_ne!(a, b);
+    assert_ne!(a, ids.encode([v, 9]).as_str());
+    assert_eq!([v, 9], ids.decode_with_tweak(a, b"tenant-a").unwrap());
+    assert_eq!([v, 9], ids.decode_with_tweak(b, b"tenant-b").unwrap());
+    if let Ok(other) = ids.decode_with_tweak::<2>(&a, b"tenant-b") {
+      assert_ne!([v, 9], other);
+    }
+  }
+}
+
+#[test]
+fn tweaks_with_a_mac_reject_each_other() {
+  let ids = HashIdB32::with_salt(b"shared").with_mac(*b"sixteen byte key", 3);
+  for v in 0..500u64 {
+    let id = ids.encode_with_tweak([v], b"tenant-a").unwrap();
+    assert!(ids.decode_with_tweak::<1>(&id, b"tenant-b").is_err());
+    assert!(ids.decode::<1>(&id).is_err());
+  }
+}
+
+#[test]
+fn tweak_survives_long_salts() {
+  let salt = [7u8; 64];
+  let ids = HashIdQr::with_salt(&salt);
+  let a = ids.encode_with_tweak([1234], b"x").unwrap();
+  let b = ids.encode_with_tweak([1234], b"y").unwrap();
+  assert_ne!(a, b);
+}
+
+#[test]
+fn tweaks_move_the_lottery() {
+  let ids = HashIdDefault::with_salt(b"shared");
+  for v in [0, 1, 42, 1 << 40] {
+    let firsts: std::collections::HashSet<u8> = (0..20u8)
+      .map(|t| ids.encode_with_tweak([v], &[t]).unwrap().as_bytes()[0])
+      .collect();
+    assert!(firsts.len() > 10, "{} first characters for {}", firsts.len(), v);
+  }
+}
@@ -0,0 +1,66 @@
+use hashid_stack::{
+  any::{AnyHashId, Variant},
+  prelude::*,
+  versioned::Versioned,
+};
+
+fn versions() -> [(u8, AnyHashId); 3] {
+  [
+    (b'a', AnyHashId::new(Variant::Qr, b"first", None).unwrap()),
+    (b'b', AnyHashId::new(Variant::B64, b"second", 6).unwrap()),
+    (b'c', AnyHashId::new(Variant::B32, b"third", 10).unwrap().with_context(b"user")),
+  ]
+}
+
+#[test]
+fn encodes_with_current() {
+  let ids = Versioned::new(versions(), b'b').unwrap();
+  let id = ids.encode([1, 2]);
+  assert_eq!(format!("b{}", versions()[1].1.encode([1, 2])), id);
+  assert_eq!(b'b', ids.current().0);
+  assert_eq!(Variant::B64, ids.current().1.variant());
+  assert_eq!(&*id, ids.encode_buf([1, 2]).unwrap().as_str());
+}
+
+#[test]
+fn decodes_every_version() {
+  for (symbol, _) in versions() {
+    let writer = Versioned::new(versions(), symbol).unwrap();
+    let reader = Versioned::new(versions(), b'c').unwrap();
+    assert_eq!(Ok(([7, 8], symbol)), reader.decode(writer.encode([7, 8])).map_err(drop));
+  }
+}
+
+#[test]
+fn trailing_symbol() {
+  let ids = Versioned::new(versions(), b'c').unwrap().trailing();
+  let id = ids.encode([3]);
+  assert!(id.ends_with('c'));
+  assert_eq!(format!("{}c", versions()[2].1.encode([3])), id);
+  assert_eq!(([3], b'c'), ids.decode(&id).unwrap());
+}
+
+#[test]
+fn rejects_unknown_versions() {
+  let ids = Versioned::new(versions(), b'a').unwrap();
+  let id = ids.encode([5]);
+  let swapped = format!("z{}", &id[1..]);
+  assert!(matches!(ids.decode::<1>(&swapped), Err(DecodeErr::Version(b'z'))));
+  assert!(matches!(ids.decode::<1>(""), Err(DecodeErr::Hash)));
+  assert_eq!("unknown version 'z'", ids.decode::<1>(&swapped).unwrap_err().to_string());
+
+  // another version's character on an ID it didn't encode
+  let other = format!("b{}", &id[1..]);
+  assert_ne!(Some([5]), ids.decode::<1>(&other).ok().map(|(v, _)| v));
+}
+
+#[test]
+fn validates_symbols() {
+  let [a, b, _] = versions();
+  assert!(Versioned::new([a, b], b'c').is_none());
+  assert!(Versioned::new([a, (b'a', b.1)], b'a').is_none());
+  assert!(Versioned::new([a, (b' ', b.1)], b'a').is_none());
+  assert!(Versioned::new([a, (0xe9, b.1)], b'a').is_none());
+  assert!(Versioned::new([a, b], b'a').is_some());
+  assert!(Versioned::new([a, b], b'a').unwrap().get(b'c').is_none());
+}